@@ -1,6 +1,32 @@
 use super::point::{Point, PointType};
-use crate::finite_fields::element::Felt;
-use color_eyre::eyre::Result;
+use crate::{
+    finite_fields::{element::Felt, pow::Pow},
+    helpers::arithmetic::{factorize, gcd},
+};
+use color_eyre::eyre::{eyre, Result};
+use num_bigint::BigUint;
+use std::collections::HashMap;
+
+/// Largest prime `Curve::count_points` enumerates naively, above it Mestre's
+/// baby-step giant-step method is used
+pub const NAIVE_COUNT_MAX_PRIME: u64 = 1 << 12;
+
+/// Largest prime `Curve::count_points` accepts, cryptographic sizes need Schoof's
+/// algorithm (SEA) which is not implemented
+pub const MAX_COUNT_PRIME_BITS: u64 = 48;
+
+/// Represents the structure of the group of points of a curve
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveAnalysis {
+    /// 4a^3 + 27b^2 = 0, the curve has a cusp or a node and is not an elliptic curve
+    pub singular: bool,
+    /// Number of points including infinity, `None` for singular curves
+    pub points: Option<BigUint>,
+    /// Number of points equals p, discrete logs are easy with Smart's attack
+    pub anomalous: bool,
+    /// Prime factorization of the number of points
+    pub factors: Vec<(u64, u32)>,
+}
 
 /// Represents an elliptic curve
 /// ( y^2 = x^3 + ax + b )
@@ -16,17 +42,254 @@ impl Curve {
         Self { a, b }
     }
 
-    /// Creates a point on the curve 
-    /// 
+    /// Creates a point on the curve
+    ///
     /// # Errors
-    /// 
+    ///
     /// Propagates if `Point::try_from_felts` returns an error
     pub fn point(&self, x: Felt, y: Felt) -> Result<Point> {
         Point::try_from_felts(x, y, self.a.clone(), self.b.clone())
     }
 
+    /// Recovers a point from its x coordinate and the parity of its y coordinate
+    ///
+    /// This is how compressed SEC points are decompressed, and works for any prime
+    /// since square roots are computed with Tonelli-Shanks
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no point with the given x coordinate
+    pub fn point_from_x(&self, x: Felt, is_even: bool) -> Result<Point> {
+        let y_squared = x.pow(3u32) + &self.a * &x + &self.b;
+        let y = y_squared
+            .sqrt()
+            .ok_or_else(|| eyre!("No point on the curve with x = {}", x.inner()))?;
+
+        let y_is_even = !y.inner().bit(0);
+        let y = if y_is_even == is_even {
+            y
+        } else {
+            Felt::new(BigUint::from(0u32), y.prime().clone()) - y
+        };
+
+        self.point(x, y)
+    }
+
     /// Returns the identity point of the curve which is (Inf, Inf)
     pub fn identity(&self) -> Point {
         Point::new(PointType::Infinity, PointType::Infinity, self.clone())
     }
+
+    /// Returns the prime of the field the curve is defined over
+    pub fn prime(&self) -> &BigUint {
+        self.a.prime()
+    }
+
+    /// Checks if the discriminant 4a^3 + 27b^2 is zero
+    ///
+    /// Such curves have a cusp or a node, so the chord and tangent rule doesn't give
+    /// a group and discrete logs map to much easier problems
+    pub fn is_singular(&self) -> bool {
+        let discriminant = self.a.pow(3u32) * 4u32 + self.b.pow(2u32) * 27u32;
+        discriminant.is_zero()
+    }
+
+    /// Returns the quadratic twist y^2 = x^3 + a d^2 x + b d^3 for a non-residue d
+    ///
+    /// For every x, x^3 + ax + b is a square on exactly one of the curve and its twist
+    /// (unless it's zero), so #E + #E' = 2p + 2
+    pub fn twist(&self) -> Curve {
+        let prime = self.prime().clone();
+        let mut d = Felt::new(BigUint::from(2u32), prime.clone());
+        while d.is_square() {
+            d = d + Felt::new(BigUint::from(1u32), prime.clone());
+        }
+
+        Curve::new(&self.a * d.pow(2u32), &self.b * d.pow(3u32))
+    }
+
+    /// Returns the number of points on the curve, including infinity
+    ///
+    /// Small curves are enumerated, mid-sized ones use Mestre's method
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the curve is singular or the prime is too large
+    pub fn count_points(&self) -> Result<BigUint> {
+        if self.small_prime()? <= NAIVE_COUNT_MAX_PRIME {
+            self.count_points_naive()
+        } else {
+            self.count_points_mestre()
+        }
+    }
+
+    /// Counts the points by going through every x
+    ///
+    /// Each x has 1 + (x^3 + ax + b | p) points, so
+    /// #E = 1 + p + sum of Legendre symbols. Needs O(p) steps
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the curve is singular or the prime is too large
+    pub fn count_points_naive(&self) -> Result<BigUint> {
+        let p = self.small_prime()?;
+        let prime = self.prime().clone();
+
+        let mut count = i128::from(p) + 1;
+        for x in 0..p {
+            let x = Felt::new(BigUint::from(x), prime.clone());
+            let y_squared = x.pow(3u32) + &self.a * &x + &self.b;
+            count += i128::from(y_squared.legendre());
+        }
+
+        Ok(BigUint::from(u128::try_from(count)?))
+    }
+
+    /// Counts the points with Mestre's baby-step giant-step method
+    /// <https://en.wikipedia.org/wiki/Counting_points_on_elliptic_curves#Baby-step_giant-step>
+    ///
+    /// By Hasse's theorem #E is in [p + 1 - 2 sqrt(p), p + 1 + 2 sqrt(p)]. For a point P
+    /// we find the multiples of its order in that interval with baby-step giant-step,
+    /// and combine orders of several points until only one candidate is left. Mestre
+    /// showed that this works on either the curve or its twist, since #E' = 2p + 2 - #E.
+    /// Needs O(p^(1/4)) steps per point
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the curve is singular, the prime is too large or no unique
+    /// candidate is found
+    pub fn count_points_mestre(&self) -> Result<BigUint> {
+        let p = self.small_prime()?;
+        let width = 2 * (p.isqrt() + 1);
+        let (low, high) = ((p + 1).saturating_sub(width), p + 1 + width);
+
+        let twist = self.twist();
+        let mut lcm = 1u64;
+        let mut twist_lcm = 1u64;
+
+        for (point, twist_point) in self.points_by_x().zip(twist.points_by_x()).take(64) {
+            lcm = lcm_with_order(lcm, &point, low, high)?;
+            if let Some(count) = unique_multiple(lcm, low, high) {
+                return Ok(BigUint::from(count));
+            }
+
+            twist_lcm = lcm_with_order(twist_lcm, &twist_point, low, high)?;
+            if let Some(count) = unique_multiple(twist_lcm, low, high) {
+                return Ok(BigUint::from(2 * p + 2 - count));
+            }
+        }
+
+        Err(eyre!("Could not narrow down the number of points"))
+    }
+
+    /// Checks if the number of points equals the prime
+    ///
+    /// # Errors
+    ///
+    /// Propagates if `count_points` returns an error
+    pub fn is_anomalous(&self) -> Result<bool> {
+        Ok(&self.count_points()? == self.prime())
+    }
+
+    /// Reports the group structure of the curve and its weaknesses
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the prime is too large to count points
+    pub fn analyze(&self) -> Result<CurveAnalysis> {
+        if self.is_singular() {
+            return Ok(CurveAnalysis {
+                singular: true,
+                points: None,
+                anomalous: false,
+                factors: Vec::new(),
+            });
+        }
+
+        let points = self.count_points()?;
+        let factors = factorize(u64::try_from(&points)?);
+        Ok(CurveAnalysis {
+            singular: false,
+            anomalous: &points == self.prime(),
+            points: Some(points),
+            factors,
+        })
+    }
+
+    /// Returns the prime as a `u64` if the curve is small enough to count points
+    fn small_prime(&self) -> Result<u64> {
+        if self.is_singular() {
+            return Err(eyre!("Curve is singular"));
+        }
+        if self.prime().bits() > MAX_COUNT_PRIME_BITS {
+            return Err(eyre!(
+                "Counting points over a {} bit prime needs Schoof's algorithm",
+                self.prime().bits()
+            ));
+        }
+
+        Ok(u64::try_from(self.prime())?)
+    }
+
+    /// Returns points of the curve with increasing x coordinates
+    fn points_by_x(&self) -> impl Iterator<Item = Point> + '_ {
+        let prime = self.prime().clone();
+        num_iter(&prime)
+            .filter_map(move |x| self.point_from_x(Felt::new(x, prime.clone()), true).ok())
+    }
+}
+
+/// Iterates over 0..n
+fn num_iter(n: &BigUint) -> impl Iterator<Item = BigUint> {
+    let n = n.clone();
+    std::iter::successors(Some(BigUint::from(0u32)), |x| Some(x + 1u32)).take_while(move |x| x < &n)
+}
+
+/// Returns all N in [low, high] with N * P = O using baby-step giant-step
+///
+/// Write N = low + i * m + j, then j * P = -(low * P + i * m * P)
+fn multiples_in_interval(point: &Point, low: u64, high: u64) -> Vec<u64> {
+    let m = (high - low).isqrt() + 1;
+
+    let mut baby_steps: HashMap<_, Vec<u64>> = HashMap::new();
+    let mut current = point.curve.identity();
+    for j in 0..m {
+        baby_steps.entry(current.key()).or_default().push(j);
+        current = current + point;
+    }
+
+    let giant_step = point.clone() * BigUint::from(m);
+    let mut giant = point.clone() * BigUint::from(low);
+    let mut multiples = Vec::new();
+    for i in 0..=m {
+        if let Some(js) = baby_steps.get(&(-&giant).key()) {
+            multiples.extend(
+                js.iter()
+                    .map(|j| low + i * m + j)
+                    .filter(|candidate| *candidate <= high),
+            );
+        }
+        giant = giant + &giant_step;
+    }
+
+    multiples.sort_unstable();
+    multiples.dedup();
+    multiples
+}
+
+/// Combines the running lcm with the order of the point
+fn lcm_with_order(lcm: u64, point: &Point, low: u64, high: u64) -> Result<u64> {
+    let multiple = multiples_in_interval(point, low, high)
+        .first()
+        .copied()
+        .ok_or_else(|| eyre!("Point order has no multiple in the Hasse interval"))?;
+    let order = u64::try_from(&point.order_in(&BigUint::from(multiple))?)?;
+
+    Ok(lcm / gcd(lcm, order) * order)
+}
+
+/// Returns the multiple of n in [low, high] if there is exactly one
+fn unique_multiple(n: u64, low: u64, high: u64) -> Option<u64> {
+    let first = low.div_ceil(n) * n;
+    (first <= high && first + n > high).then_some(first)
 }
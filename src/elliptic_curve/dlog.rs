@@ -0,0 +1,343 @@
+use super::point::{Point, PointType};
+use crate::helpers::arithmetic::{factorize, gcd};
+use color_eyre::eyre::{eyre, Result};
+use num_bigint::BigUint;
+use std::collections::HashMap;
+
+/// Largest group order baby-step giant-step accepts, its table has sqrt(n) entries
+pub const MAX_BSGS_ORDER_BITS: u64 = 40;
+
+/// Largest group order Pollard's rho and Pohlig-Hellman accept, so scalars fit in a `u64`
+pub const MAX_RHO_ORDER_BITS: u64 = 63;
+
+/// Largest number of candidates Pollard's rho checks for a collision, above it the
+/// walk is restarted from another point rather than enumerating gcd(b, n) of them
+const MAX_RHO_CANDIDATES: u64 = 64;
+
+/// Represents a solution k of Q = k * P and the work it took to find it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dlog {
+    pub k: BigUint,
+    /// Number of point additions performed, a scalar multiplication by a b-bit
+    /// scalar counts as 2b additions
+    pub group_operations: u64,
+}
+
+/// Returns the approximate number of group operations generic algorithms need to
+/// solve a discrete log in a group of the given order, which is sqrt(n)
+///
+/// For secp256k1 this is about 2^128, far beyond anything computable
+pub fn expected_work(order: &BigUint) -> BigUint {
+    order.sqrt()
+}
+
+/// Solves Q = k * P with baby-step giant-step
+/// <https://en.wikipedia.org/wiki/Baby-step_giant-step>
+///
+/// Write k = i * m + j with m = ceil(sqrt(n)). Store the baby steps j * P in a table,
+/// then take giant steps Q - i * m * P until one of them is in the table.
+/// Needs O(sqrt(n)) time and memory
+///
+/// # Errors
+///
+/// Returns an error if the order is below 2 or too large, or Q is not a multiple of P
+pub fn baby_step_giant_step(p: &Point, q: &Point, order: &BigUint) -> Result<Dlog> {
+    let n = small_order(order, MAX_BSGS_ORDER_BITS)?;
+    let mut work = Work::default();
+
+    let m = BigUint::from(n)
+        .sqrt()
+        .to_u64_digits()
+        .first()
+        .copied()
+        .unwrap_or_default()
+        + 1;
+
+    let mut baby_steps = HashMap::new();
+    let mut current = p.curve.identity();
+    for j in 0..m {
+        baby_steps.entry(current.key()).or_insert(j);
+        current = current + p;
+        work.add(1);
+    }
+
+    // Giant step is -m * P = (n - m) * P
+    let giant_step = work.mul(p, (n - m % n) % n);
+    let mut gamma = q.clone();
+    for i in 0..m {
+        if let Some(j) = baby_steps.get(&gamma.key()) {
+            return work.finish(
+                p,
+                q,
+                (u128::from(i) * u128::from(m) + u128::from(*j)) % u128::from(n),
+            );
+        }
+        gamma = gamma + &giant_step;
+        work.add(1);
+    }
+
+    Err(eyre!("Q is not a multiple of P"))
+}
+
+/// Solves Q = k * P with Pollard's rho
+/// <https://en.wikipedia.org/wiki/Pollard%27s_rho_algorithm_for_logarithms>
+///
+/// Walks the pseudo random sequence X = a * P + b * Q, split in three partitions by
+/// the x coordinate, until it cycles (Floyd). A collision a1 P + b1 Q = a2 P + b2 Q
+/// gives (b1 - b2) k = a2 - a1 (mod n). Needs O(sqrt(n)) time and constant memory
+///
+/// # Errors
+///
+/// Returns an error if the order is below 2 or too large, or Q is not a multiple of P
+#[allow(clippy::many_single_char_names)]
+pub fn pollard_rho(p: &Point, q: &Point, order: &BigUint) -> Result<Dlog> {
+    let n = small_order(order, MAX_RHO_ORDER_BITS)?;
+    let mut work = Work::default();
+
+    if is_identity(q) {
+        return work.finish(p, q, 0);
+    }
+
+    // Different starting points, in case a walk collides without revealing k
+    for attempt in 0..32u64 {
+        let a0 = (attempt * 7 + 1) % n;
+        let b0 = (attempt * 13 + 2) % n;
+        let start = work.mul(p, a0) + work.mul(q, b0);
+        work.add(1);
+
+        let mut tortoise = Walk {
+            point: start,
+            a: a0,
+            b: b0,
+        };
+        let mut hare = tortoise.clone();
+
+        for _ in 0..n.saturating_mul(4).saturating_add(16) {
+            tortoise = tortoise.step(p, q, n, &mut work);
+            hare = hare.step(p, q, n, &mut work).step(p, q, n, &mut work);
+
+            if tortoise.point.key() == hare.point.key() {
+                // (b1 - b2) k = a2 - a1 (mod n)
+                let b = sub_mod(tortoise.b, hare.b, n);
+                let a = sub_mod(hare.a, tortoise.a, n);
+
+                for k in solve_linear(b, a, n) {
+                    if work.mul(p, k) == *q {
+                        return work.finish(p, q, u128::from(k));
+                    }
+                }
+                break;
+            }
+        }
+    }
+
+    Err(eyre!("Q is not a multiple of P"))
+}
+
+/// Solves Q = k * P with Pohlig-Hellman, reducing the problem to the prime power
+/// subgroups of the order
+/// <https://en.wikipedia.org/wiki/Pohlig%E2%80%93Hellman_algorithm>
+///
+/// For each q^e dividing n, k mod q^e is found digit by digit in base q with
+/// baby-step giant-step in the subgroup of order q, then the results are combined
+/// with the Chinese remainder theorem. Only the largest prime factor matters for
+/// the cost, which is why cryptographic curves use prime order groups
+///
+/// # Errors
+///
+/// Returns an error if the order is below 2 or too large, or Q is not a multiple of P
+///
+/// # Panics
+///
+/// Never in practice, every digit found is less than its prime
+#[allow(clippy::many_single_char_names)]
+pub fn pohlig_hellman(p: &Point, q: &Point, order: &BigUint) -> Result<Dlog> {
+    let n = small_order(order, MAX_RHO_ORDER_BITS)?;
+    let mut work = Work::default();
+
+    let mut k = 0u128;
+    let mut modulus = 1u128;
+
+    for (prime, exponent) in factorize(n) {
+        let prime_power = prime.pow(exponent);
+        let cofactor = n / prime_power;
+
+        // P_i and Q_i live in the subgroup of order q^e
+        let p_i = work.mul(p, cofactor);
+        let q_i = work.mul(q, cofactor);
+
+        // H has order q, the base for each digit
+        let h = work.mul(&p_i, prime_power / prime);
+
+        let mut x = 0u64;
+        let mut digit_weight = 1u64;
+        for j in 0..exponent {
+            // Remove the known digits and project to the subgroup of order q
+            let remaining =
+                q_i.clone() + work.mul(&p_i, (prime_power - x % prime_power) % prime_power);
+            work.add(1);
+            let q_j = work.mul(&remaining, prime.pow(exponent - 1 - j));
+
+            let digit = baby_step_giant_step(&h, &q_j, &BigUint::from(prime))?;
+            work.add(digit.group_operations);
+            let digit = u64::try_from(&digit.k).expect("Digit is less than the prime");
+
+            x += digit * digit_weight;
+            digit_weight *= prime;
+        }
+
+        // Chinese remainder theorem: k = x (mod q^e)
+        let prime_power = u128::from(prime_power);
+        let x = u128::from(x);
+        let inverse = u128::from(inverse_mod(
+            u64::try_from(modulus % prime_power).expect("Reduced below a u64"),
+            u64::try_from(prime_power).expect("Prime power divides a u64"),
+        ));
+        let t = ((x + prime_power - k % prime_power) % prime_power) * inverse % prime_power;
+        k += modulus * t;
+        modulus *= prime_power;
+    }
+
+    work.finish(p, q, k)
+}
+
+/// Point on the walk of Pollard's rho, X = a * P + b * Q
+#[derive(Debug, Clone)]
+struct Walk {
+    point: Point,
+    a: u64,
+    b: u64,
+}
+
+impl Walk {
+    /// Moves to the next point based on the partition of X
+    /// - X + P
+    /// - 2 * X
+    /// - X + Q
+    fn step(self, p: &Point, q: &Point, n: u64, work: &mut Work) -> Self {
+        let partition = match &self.point.x {
+            PointType::Normal(x) => (x.inner() % 3u32)
+                .to_u64_digits()
+                .first()
+                .copied()
+                .unwrap_or_default(),
+            PointType::Infinity => 0,
+        };
+        work.add(1);
+
+        match partition {
+            0 => Self {
+                point: self.point + p,
+                a: (self.a + 1) % n,
+                b: self.b,
+            },
+            1 => Self {
+                point: &self.point + &self.point,
+                a: add_mod(self.a, self.a, n),
+                b: add_mod(self.b, self.b, n),
+            },
+            _ => Self {
+                point: self.point + q,
+                a: self.a,
+                b: (self.b + 1) % n,
+            },
+        }
+    }
+}
+
+/// Counts the group operations of a solver
+#[derive(Debug, Default)]
+struct Work(u64);
+
+impl Work {
+    fn add(&mut self, operations: u64) {
+        self.0 += operations;
+    }
+
+    /// Multiplies a point by a scalar, counting the double and add steps
+    fn mul(&mut self, point: &Point, scalar: u64) -> Point {
+        self.add(2 * u64::from(64 - scalar.leading_zeros()));
+        point.clone() * BigUint::from(scalar)
+    }
+
+    /// Checks the solution and wraps it with the work done
+    fn finish(mut self, p: &Point, q: &Point, k: u128) -> Result<Dlog> {
+        let k = BigUint::from(k);
+        self.add(2 * k.bits());
+        if p.clone() * k.clone() == *q {
+            Ok(Dlog {
+                k,
+                group_operations: self.0,
+            })
+        } else {
+            Err(eyre!("Q is not a multiple of P"))
+        }
+    }
+}
+
+/// Returns the order as a `u64`, or an error explaining why it is out of reach
+fn small_order(order: &BigUint, max_bits: u64) -> Result<u64> {
+    if *order < BigUint::from(2u32) {
+        return Err(eyre!("Group order must be at least 2"));
+    }
+
+    if order.bits() > max_bits {
+        return Err(eyre!(
+            "Group order has {} bits, solving would take about 2^{} group operations",
+            order.bits(),
+            expected_work(order).bits()
+        ));
+    }
+
+    u64::try_from(order).map_err(|_| eyre!("Group order is too large"))
+}
+
+fn is_identity(point: &Point) -> bool {
+    point.key().is_none()
+}
+
+fn add_mod(a: u64, b: u64, n: u64) -> u64 {
+    u64::try_from((u128::from(a) + u128::from(b)) % u128::from(n)).expect("Reduced below n")
+}
+
+fn sub_mod(a: u64, b: u64, n: u64) -> u64 {
+    add_mod(a, n - b % n, n)
+}
+
+fn mul_mod(a: u64, b: u64, n: u64) -> u64 {
+    u64::try_from(u128::from(a) * u128::from(b) % u128::from(n)).expect("Reduced below n")
+}
+
+/// Returns the inverse of a modulo n, a and n must be coprime
+fn inverse_mod(a: u64, n: u64) -> u64 {
+    // Extended Euclidean algorithm
+    let (mut old_r, mut r) = (i128::from(a), i128::from(n));
+    let (mut old_s, mut s) = (1i128, 0i128);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+
+    u64::try_from(old_s.rem_euclid(i128::from(n))).expect("Reduced below n")
+}
+
+/// Returns all solutions of b * k = a (mod n)
+///
+/// There are gcd(b, n) of them if it divides a, none otherwise. None are returned
+/// either if there are more than `MAX_RHO_CANDIDATES`
+fn solve_linear(b: u64, a: u64, n: u64) -> Vec<u64> {
+    let d = gcd(b, n);
+    if d == 0 || d > MAX_RHO_CANDIDATES || !a.is_multiple_of(d) {
+        return Vec::new();
+    }
+
+    let reduced_n = n / d;
+    let k0 = mul_mod(
+        a / d,
+        inverse_mod((b / d) % reduced_n, reduced_n),
+        reduced_n,
+    );
+    (0..d).map(|i| k0 + i * reduced_n).collect()
+}
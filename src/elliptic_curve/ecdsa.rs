@@ -0,0 +1,157 @@
+use super::{
+    jacobian::JacobianPoint,
+    params::CurveParams,
+    point::{Point, PointType},
+    rfc6979::Rfc6979,
+};
+use crate::finite_fields::modulo::Modulo;
+use color_eyre::eyre::{eyre, Result};
+use num_bigint::BigUint;
+use sha2::Sha256;
+use std::fmt::Display;
+
+/// Represents an ECDSA signature on any curve, as scalars modulo the order n
+#[derive(Debug, Clone, PartialEq)]
+pub struct EcdsaSignature {
+    r: BigUint,
+    s: BigUint,
+}
+
+impl EcdsaSignature {
+    /// Creates a new signature
+    pub fn new(r: BigUint, s: BigUint) -> Self {
+        Self { r, s }
+    }
+
+    /// Returns r of the signature
+    pub fn r(&self) -> &BigUint {
+        &self.r
+    }
+
+    /// Returns s of the signature
+    pub fn s(&self) -> &BigUint {
+        &self.s
+    }
+
+    /// Verifies the signature, given the message hash `z` and the public key
+    ///
+    /// u = z / s, v = r / s and the signature is valid if (u * G + v * P).x = r
+    #[allow(clippy::many_single_char_names)]
+    pub fn verify(&self, params: &CurveParams, z: &BigUint, public_key: &Point) -> bool {
+        let n = &params.order;
+        let zero = BigUint::from(0u32);
+
+        if self.r == zero || &self.r >= n || self.s == zero || &self.s >= n {
+            return false;
+        }
+
+        let s_inverse = scalar_inverse(&self.s, n);
+        let u = (z * &s_inverse).modulo(n);
+        let v = (&self.r * &s_inverse).modulo(n);
+
+        let total = multiply(&params.generator, &u) + multiply(public_key, &v);
+        match total.to_affine().x {
+            PointType::Infinity => false,
+            PointType::Normal(x) => x.inner().modulo(n) == self.r,
+        }
+    }
+}
+
+impl Display for EcdsaSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Signature(0x{:x}, 0x{:x})", self.r, self.s)
+    }
+}
+
+/// Represents an ECDSA private key on any curve described by `CurveParams`
+///
+/// The order of the generator must be prime, otherwise k and s can't be inverted
+#[derive(Debug, Clone)]
+pub struct SigningKey {
+    params: &'static CurveParams,
+    secret: BigUint,
+    public_key: Point,
+}
+
+impl SigningKey {
+    /// Creates a new signing key from a secret scalar
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret is not in [1, n-1]
+    pub fn new(params: &'static CurveParams, secret: BigUint) -> Result<Self> {
+        if secret == BigUint::from(0u32) || secret >= params.order {
+            return Err(eyre!("Secret must be between 1 and n-1"));
+        }
+
+        let public_key = multiply(&params.generator, &secret).to_affine();
+        Ok(Self {
+            params,
+            secret,
+            public_key,
+        })
+    }
+
+    /// Returns the curve parameters of the key
+    pub fn params(&self) -> &'static CurveParams {
+        self.params
+    }
+
+    /// Returns the public key for this private key
+    pub fn public_key(&self) -> &Point {
+        &self.public_key
+    }
+
+    /// Signs the message hash `z` with a deterministic k (RFC 6979)
+    ///
+    /// # Panics
+    ///
+    /// Never in practice, the nonce generator yields values until one of them works
+    pub fn sign(&self, z: &BigUint) -> EcdsaSignature {
+        Rfc6979::<Sha256>::from_z(&self.params.order, &self.secret, z, None)
+            .find_map(|k| self.sign_with_k(z, &k))
+            .expect("Nonce generator never ends")
+    }
+
+    /// Signs the message hash `z` with the given k
+    ///
+    /// r = (k * G).x, s = (z + r * secret) / k
+    ///
+    /// Returns `None` if k is out of range or r or s end up being zero
+    #[allow(clippy::many_single_char_names)]
+    pub fn sign_with_k(&self, z: &BigUint, k: &BigUint) -> Option<EcdsaSignature> {
+        let n = &self.params.order;
+        let zero = BigUint::from(0u32);
+
+        if k == &zero || k >= n {
+            return None;
+        }
+
+        let r = match multiply(&self.params.generator, k).to_affine().x {
+            PointType::Normal(x) => x.inner().modulo(n),
+            PointType::Infinity => return None,
+        };
+
+        let s = ((z + &r * &self.secret) * scalar_inverse(k, n)).modulo(n);
+        if r == zero || s == zero {
+            return None;
+        }
+
+        Some(EcdsaSignature::new(r, s))
+    }
+
+    /// Checks if message is signed by this private key
+    pub fn verify(&self, z: &BigUint, signature: &EcdsaSignature) -> bool {
+        signature.verify(self.params, z, &self.public_key)
+    }
+}
+
+/// Multiplies a point by a scalar in Jacobian coordinates
+fn multiply(point: &Point, scalar: &BigUint) -> JacobianPoint {
+    JacobianPoint::from(point.clone()).mul(scalar)
+}
+
+/// Inverts a scalar modulo the (prime) order n
+fn scalar_inverse(value: &BigUint, n: &BigUint) -> BigUint {
+    value.modpow(&(n - BigUint::from(2u32)), n)
+}
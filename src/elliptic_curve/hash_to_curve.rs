@@ -0,0 +1,281 @@
+use super::{
+    params::CurveParams,
+    point::{Point, PointType},
+};
+use crate::finite_fields::{element::Felt, modulo::Modulo, pow::Pow};
+use color_eyre::eyre::{eyre, Result};
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+
+/// Suite of `Secp256k1Point::hash_to_curve`, random oracle encoding
+pub const SECP256K1_RO_SUITE: &str = "secp256k1_XMD:SHA-256_SSWU_RO_";
+
+/// Suite of `Secp256k1Point::encode_to_curve`, non-uniform encoding
+pub const SECP256K1_NU_SUITE: &str = "secp256k1_XMD:SHA-256_SSWU_NU_";
+
+/// Bytes hashed per field element, ceil((ceil(log2(p)) + k) / 8) with k = 128
+const L: usize = 48;
+
+/// Input block size of SHA-256
+const SHA256_BLOCK_SIZE: usize = 64;
+
+/// Output size of SHA-256
+const SHA256_OUTPUT_SIZE: usize = 32;
+
+/// Expands the message into `len` uniformly random bytes with SHA-256
+/// <https://www.rfc-editor.org/rfc/rfc9380#section-5.3.1>
+///
+/// Domain separation tags longer than 255 bytes are hashed first, as the RFC requires
+///
+/// # Errors
+///
+/// Returns an error if more than 255 hash blocks or 65535 bytes are requested
+///
+/// # Panics
+///
+/// Never in practice, lengths are checked before they are converted
+pub fn expand_message_xmd(message: &[u8], dst: &[u8], len: usize) -> Result<Vec<u8>> {
+    let ell = len.div_ceil(SHA256_OUTPUT_SIZE);
+    if ell > 255 || len > usize::from(u16::MAX) {
+        return Err(eyre!("Requested too many bytes, {len}"));
+    }
+
+    let dst = if dst.len() > 255 {
+        Sha256::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize()
+            .to_vec()
+    } else {
+        dst.to_vec()
+    };
+    let mut dst_prime = dst;
+    dst_prime.push(u8::try_from(dst_prime.len()).expect("DST is at most 255 bytes"));
+
+    let len_bytes = u16::try_from(len).expect("Length is checked").to_be_bytes();
+
+    // b_0 = H(Z_pad || msg || l_i_b_str || I2OSP(0, 1) || DST_prime)
+    let b_0 = Sha256::new()
+        .chain_update([0u8; SHA256_BLOCK_SIZE])
+        .chain_update(message)
+        .chain_update(len_bytes)
+        .chain_update([0])
+        .chain_update(&dst_prime)
+        .finalize();
+
+    // b_1 = H(b_0 || I2OSP(1, 1) || DST_prime)
+    // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime)
+    let mut uniform = Vec::with_capacity(ell * SHA256_OUTPUT_SIZE);
+    let mut b_i = vec![0u8; SHA256_OUTPUT_SIZE];
+    for i in 1..=ell {
+        let xored: Vec<u8> = b_0.iter().zip(&b_i).map(|(a, b)| a ^ b).collect();
+        b_i = Sha256::new()
+            .chain_update(xored)
+            .chain_update([u8::try_from(i).expect("At most 255 blocks")])
+            .chain_update(&dst_prime)
+            .finalize()
+            .to_vec();
+        uniform.extend_from_slice(&b_i);
+    }
+
+    uniform.truncate(len);
+    Ok(uniform)
+}
+
+/// Hashes the message to `count` elements of the secp256k1 base field
+/// <https://www.rfc-editor.org/rfc/rfc9380#section-5.2>
+///
+/// # Errors
+///
+/// Returns an error if `count` is too large for `expand_message_xmd`
+pub fn hash_to_field(message: &[u8], dst: &[u8], count: usize) -> Result<Vec<Felt>> {
+    let prime = CurveParams::secp256k1().prime();
+    let uniform = expand_message_xmd(message, dst, count * L)?;
+
+    Ok(uniform
+        .chunks(L)
+        .map(|chunk| Felt::new(BigUint::from_bytes_be(chunk).modulo(prime), prime.clone()))
+        .collect())
+}
+
+/// Maps a field element to a point on secp256k1
+///
+/// secp256k1 has a = 0, so the simplified SWU map is computed on the isogenous curve
+/// E': y^2 = x^3 + A'x + B' and the point is moved to secp256k1 with a 3-isogeny
+/// <https://www.rfc-editor.org/rfc/rfc9380#section-6.6.3>
+pub fn map_to_curve(u: &Felt) -> Point {
+    let (x, y) = map_to_curve_simple_swu(u);
+    iso_map(&x, &y)
+}
+
+/// Simplified Shallue-van de Woestijne-Ulas method on E'
+/// <https://www.rfc-editor.org/rfc/rfc9380#section-6.6.2>
+///
+/// Returns the affine coordinates of the point on E', which never is the identity
+#[allow(clippy::many_single_char_names)]
+fn map_to_curve_simple_swu(u: &Felt) -> (Felt, Felt) {
+    let constants = IsogenyConstants::get();
+    let (a, b, z) = (&constants.a, &constants.b, &constants.z);
+    let one = element(1u32);
+
+    // tv1 = inv0(Z^2 * u^4 + Z * u^2)
+    let z_u2 = z * &u.pow(2u32);
+    let denominator = &z_u2 * &z_u2 + z_u2.clone();
+    let x1 = match denominator.inverse() {
+        // x1 = (-B / A) * (1 + tv1)
+        Some(tv1) => (element(0u32) - b) / a * (one + tv1),
+        // Exceptional case, x1 = B / (Z * A)
+        None => b / &(z * a),
+    };
+    let x2 = &z_u2 * &x1;
+
+    let g = |x: &Felt| x.pow(3u32) + a * x + b.clone();
+    let (x, y) = if let Some(y) = g(&x1).sqrt() {
+        (x1, y)
+    } else {
+        let y = g(&x2).sqrt().expect("g(x1) or g(x2) is a square");
+        (x2, y)
+    };
+
+    // sgn0(y) must match sgn0(u)
+    let y = if sgn0(u) == sgn0(&y) {
+        y
+    } else {
+        element(0u32) - y
+    };
+
+    (x, y)
+}
+
+/// 3-isogeny from E' to secp256k1
+/// <https://www.rfc-editor.org/rfc/rfc9380#appendix-E.1>
+///
+/// Returns the identity if a denominator vanishes
+fn iso_map(x: &Felt, y: &Felt) -> Point {
+    let constants = IsogenyConstants::get();
+    let curve = &CurveParams::secp256k1().curve;
+
+    // Evaluates a polynomial given its coefficients from the lowest degree up
+    let eval = |coefficients: &[Felt]| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(element(0u32), |sum, coefficient| {
+                sum * x + coefficient.clone()
+            })
+    };
+
+    let x_num = eval(&constants.x_num);
+    let x_den = eval(&constants.x_den);
+    let y_num = eval(&constants.y_num);
+    let y_den = eval(&constants.y_den);
+
+    if x_den.is_zero() || y_den.is_zero() {
+        return curve.identity();
+    }
+
+    let x = x_num / x_den;
+    let y = y * &(y_num / y_den);
+    Point {
+        x: PointType::Normal(x),
+        y: PointType::Normal(y),
+        curve: curve.clone(),
+    }
+}
+
+/// Sign of a field element as defined by the RFC, its parity
+fn sgn0(value: &Felt) -> bool {
+    value.inner().bit(0)
+}
+
+/// Creates an element of the secp256k1 base field
+fn element(value: impl Into<BigUint>) -> Felt {
+    Felt::new(value.into(), CurveParams::secp256k1().prime().clone())
+}
+
+/// Constants of E' and the 3-isogeny map
+struct IsogenyConstants {
+    a: Felt,
+    b: Felt,
+    z: Felt,
+    x_num: Vec<Felt>,
+    x_den: Vec<Felt>,
+    y_num: Vec<Felt>,
+    y_den: Vec<Felt>,
+}
+
+impl IsogenyConstants {
+    fn get() -> &'static Self {
+        static CONSTANTS: OnceLock<IsogenyConstants> = OnceLock::new();
+        CONSTANTS.get_or_init(|| {
+            let parse = |values: &[&str]| -> Vec<Felt> {
+                values
+                    .iter()
+                    .map(|value| {
+                        element(BigUint::parse_bytes(value.as_bytes(), 16).expect("Invalid hex"))
+                    })
+                    .collect()
+            };
+
+            Self {
+                a: parse(&["3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533"])
+                    .remove(0),
+                b: element(1771u32),
+                z: element(0u32) - element(11u32),
+                x_num: parse(&[
+                    "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7",
+                    "07d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581",
+                    "534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262",
+                    "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c",
+                ]),
+                x_den: parse(&[
+                    "d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b",
+                    "edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14",
+                    "01",
+                ]),
+                y_num: parse(&[
+                    "4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c",
+                    "c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3",
+                    "29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931",
+                    "2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84",
+                ]),
+                y_den: parse(&[
+                    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b",
+                    "7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573",
+                    "6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f",
+                    "01",
+                ]),
+            }
+        })
+    }
+}
+
+/// Hashes the message to a point with try-and-increment
+///
+/// x = SHA256(message || counter), with the counter a 4 byte big-endian integer
+/// starting at 0 and incremented until x is the x coordinate of a point, and y is
+/// picked even as in BIP340. Takes two attempts on average and is not constant time,
+/// so it should only be used with public inputs
+///
+/// # Panics
+///
+/// Never in practice, about half of the candidates are valid
+pub fn try_and_increment(message: &[u8]) -> Point {
+    let params = CurveParams::secp256k1();
+
+    (0..=u32::MAX)
+        .find_map(|counter| {
+            let candidate = Sha256::new()
+                .chain_update(message)
+                .chain_update(counter.to_be_bytes())
+                .finalize();
+            let x = BigUint::from_bytes_be(&candidate);
+            if &x < params.prime() {
+                params.curve.point_from_x(element(x), true).ok()
+            } else {
+                None
+            }
+        })
+        .expect("A candidate out of 2^32 is on the curve")
+}
@@ -0,0 +1,188 @@
+use super::{
+    curve::Curve,
+    point::{Point, PointType},
+};
+use crate::finite_fields::{element::Felt, macros::impl_refs, pow::Pow};
+use num_bigint::BigUint;
+use std::ops::Add;
+
+/// Represents a point on an elliptic curve in Jacobian coordinates
+///
+/// (X, Y, Z) stands for the affine point (X / Z^2, Y / Z^3) and Z = 0 is the point at
+/// infinity. Addition and doubling don't need a field inversion, so a long computation
+/// only pays for one when converting back with `to_affine` or `batch_normalize`
+#[derive(Debug, Clone)]
+pub struct JacobianPoint {
+    x: Felt,
+    y: Felt,
+    z: Felt,
+    curve: Curve,
+}
+
+impl JacobianPoint {
+    /// Returns the point at infinity (1, 1, 0) of the curve
+    pub fn identity(curve: Curve) -> Self {
+        let prime = curve.a.prime().clone();
+        Self {
+            x: Felt::new(BigUint::from(1u32), prime.clone()),
+            y: Felt::new(BigUint::from(1u32), prime.clone()),
+            z: Felt::new(BigUint::from(0u32), prime),
+            curve,
+        }
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    /// Doubles the point
+    ///
+    /// Formula for (X3, Y3, Z3) = 2 * (X1, Y1, Z1)
+    ///  S = 4 * X1 * Y1^2
+    ///  M = 3 * X1^2 + a * Z1^4
+    /// X3 = M^2 - 2 * S
+    /// Y3 = M * (S - X3) - 8 * Y1^4
+    /// Z3 = 2 * Y1 * Z1
+    #[allow(clippy::many_single_char_names)]
+    pub fn double(&self) -> Self {
+        if self.is_infinity() || self.y.is_zero() {
+            return Self::identity(self.curve.clone());
+        }
+
+        let y_squared = self.y.pow(2u32);
+        let s = &self.x * &y_squared * 4u32;
+        let m = self.x.pow(2u32) * 3u32 + &self.curve.a * self.z.pow(4u32);
+        let x = m.pow(2u32) - &s * 2u32;
+        let y = &m * (&s - &x) - y_squared.pow(2u32) * 8u32;
+        let z = &self.y * &self.z * 2u32;
+
+        Self {
+            x,
+            y,
+            z,
+            curve: self.curve.clone(),
+        }
+    }
+
+    /// Scalar multiplies the point using binary expansion method
+    pub fn mul(&self, coefficient: &BigUint) -> Self {
+        let mut result = Self::identity(self.curve.clone());
+
+        for i in (0..coefficient.bits()).rev() {
+            result = result.double();
+            if coefficient.bit(i) {
+                result = result + self;
+            }
+        }
+
+        result
+    }
+
+    /// Converts the point back to affine coordinates
+    /// x = X / Z^2, y = Y / Z^3
+    pub fn to_affine(&self) -> Point {
+        Self::batch_normalize(std::slice::from_ref(self))
+            .pop()
+            .unwrap_or_else(|| self.curve.identity())
+    }
+
+    /// Converts many points to affine coordinates with a single field inversion
+    pub fn batch_normalize(points: &[JacobianPoint]) -> Vec<Point> {
+        let mut z_inverses: Vec<Felt> = points.iter().map(|point| point.z.clone()).collect();
+        Felt::batch_invert(&mut z_inverses);
+
+        points
+            .iter()
+            .zip(z_inverses)
+            .map(|(point, z_inverse)| {
+                if point.is_infinity() {
+                    return point.curve.identity();
+                }
+
+                let z_inverse_squared = z_inverse.pow(2u32);
+                let x = &point.x * &z_inverse_squared;
+                let y = &point.y * (z_inverse_squared * z_inverse);
+                Point::new(
+                    PointType::Normal(x),
+                    PointType::Normal(y),
+                    point.curve.clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+impl From<Point> for JacobianPoint {
+    fn from(point: Point) -> Self {
+        match (point.x, point.y) {
+            (PointType::Normal(x), PointType::Normal(y)) => {
+                let z = Felt::new(BigUint::from(1u32), x.prime().clone());
+                Self {
+                    x,
+                    y,
+                    z,
+                    curve: point.curve,
+                }
+            }
+            _ => Self::identity(point.curve),
+        }
+    }
+}
+
+impl Add for JacobianPoint {
+    type Output = Self;
+
+    /// Adds two points without any field inversion
+    ///
+    /// Formula for (X3, Y3, Z3) = (X1, Y1, Z1) + (X2, Y2, Z2)
+    /// U1 = X1 * Z2^2, U2 = X2 * Z1^2
+    /// S1 = Y1 * Z2^3, S2 = Y2 * Z1^3
+    ///  H = U2 - U1,    R = S2 - S1
+    /// X3 = R^2 - H^3 - 2 * U1 * H^2
+    /// Y3 = R * (U1 * H^2 - X3) - S1 * H^3
+    /// Z3 = H * Z1 * Z2
+    #[allow(clippy::many_single_char_names)]
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.is_infinity() {
+            return rhs;
+        }
+        if rhs.is_infinity() {
+            return self;
+        }
+
+        let z1_squared = self.z.pow(2u32);
+        let z2_squared = rhs.z.pow(2u32);
+        let u1 = &self.x * &z2_squared;
+        let u2 = &rhs.x * &z1_squared;
+        let s1 = &self.y * (z2_squared * &rhs.z);
+        let s2 = &rhs.y * (z1_squared * &self.z);
+
+        if u1 == u2 {
+            // Same x coordinate, either P + P or P + (-P)
+            return if s1 == s2 {
+                self.double()
+            } else {
+                Self::identity(self.curve)
+            };
+        }
+
+        let h = &u2 - &u1;
+        let r = &s2 - &s1;
+        let h_squared = h.pow(2u32);
+        let h_cubed = &h_squared * &h;
+        let u1_h_squared = &u1 * &h_squared;
+
+        let x = r.pow(2u32) - &h_cubed - &u1_h_squared * 2u32;
+        let y = &r * (&u1_h_squared - &x) - &s1 * &h_cubed;
+        let z = h * &self.z * &rhs.z;
+
+        Self {
+            x,
+            y,
+            z,
+            curve: self.curve,
+        }
+    }
+}
+
+impl_refs!(Add, add, JacobianPoint, JacobianPoint);
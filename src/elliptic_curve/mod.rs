@@ -1,6 +1,15 @@
 #![allow(unused)]
 pub mod curve;
+pub mod dlog;
+pub mod ecdsa;
+pub mod hash_to_curve;
+pub mod jacobian;
+pub mod msm;
+pub mod nonce_reuse;
+pub mod p256;
+pub mod params;
 pub mod point;
+pub mod rfc6979;
 pub mod secp256k1;
 pub mod signature;
 pub mod private_key;
@@ -9,11 +18,24 @@ pub mod private_key;
 mod tests {
     use std::str::FromStr;
 
-    use crate::{finite_fields::macros::felt, elliptic_curve::{secp256k1::Secp256k1Felt, signature::Signature}, helpers::bytes::ToBytesBigEndian};
+    use crate::{finite_fields::{macros::felt, pow::Pow}, elliptic_curve::{secp256k1::Secp256k1Felt, signature::Signature}, helpers::bytes::ToBytesBigEndian};
+    use hmac::digest::core_api::BlockSizeUser;
     use num_bigint::BigUint;
     use primitive_types::U256;
+    use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
 
-    use super::{curve::Curve, point::Point, secp256k1::Secp256k1Point, *};
+    use super::{
+        private_key::PrivateKey,
+        curve::{Curve, CurveAnalysis},
+        dlog::{baby_step_giant_step, expected_work, pohlig_hellman, pollard_rho},
+        ecdsa::{EcdsaSignature, SigningKey},
+        jacobian::JacobianPoint,
+        p256::{P256Point, P256PrivateKey, P256Signature},
+        params::CurveParams,
+        point::{Point, PointType},
+        secp256k1::{Secp256k1Fp, Secp256k1Point},
+        *,
+    };
 
     #[test]
     fn test_curve() {
@@ -34,6 +56,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_point_from_x() {
+        let prime = 223u64;
+        let curve = Curve::new(felt!(0, prime), felt!(7, prime));
+
+        let point = curve.point_from_x(felt!(192, prime), false).unwrap();
+        assert_eq!(point, curve.point(felt!(192, prime), felt!(105, prime)).unwrap());
+
+        let point = curve.point_from_x(felt!(192, prime), true).unwrap();
+        assert_eq!(point, curve.point(felt!(192, prime), felt!(118, prime)).unwrap());
+
+        assert!(curve.point_from_x(felt!(4, prime), true).is_err());
+    }
+
+    #[test]
+    fn test_sec_compressed_roundtrip() {
+        let point = Secp256k1Point::g() * 12345u32;
+        let sec = point.sec_compressed().unwrap();
+
+        assert_eq!(Secp256k1Point::sec_parse(&sec), point);
+    }
+
+    #[test]
+    fn test_try_sec_parse() {
+        let point = Secp256k1Point::g() * BigUint::from(12345u32);
+        let compressed = point.sec_compressed().unwrap();
+        let uncompressed = point.sec_uncompressed().unwrap();
+        assert_eq!(Secp256k1Point::try_sec_parse(&compressed).unwrap(), point);
+        assert_eq!(Secp256k1Point::try_sec_parse(&uncompressed).unwrap(), point);
+
+        // x below 2^248 is padded to 32 bytes
+        let short_x = std::iter::successors(Some(Secp256k1Point::g()), |point| {
+            Some(point.clone() + Secp256k1Point::g())
+        })
+        .take(5000)
+        .find(|point| matches!(point.x(), PointType::Normal(x) if x.inner().bits() <= 248))
+        .unwrap();
+        let compressed = short_x.sec_compressed().unwrap();
+        let uncompressed = short_x.sec_uncompressed().unwrap();
+        assert_eq!((compressed.len(), compressed[1]), (33, 0));
+        assert_eq!((uncompressed.len(), uncompressed[1]), (65, 0));
+        assert_eq!(Secp256k1Point::try_sec_parse(&compressed).unwrap(), short_x);
+        assert_eq!(Secp256k1Point::try_sec_parse(&uncompressed).unwrap(), short_x);
+
+        let mut off_curve = uncompressed.clone();
+        off_curve[64] ^= 1;
+        let mut hybrid = uncompressed.clone();
+        hybrid[0] = 0x06;
+        let invalid = [
+            off_curve,
+            hybrid,
+            compressed[..32].to_vec(),
+            Vec::new(),
+            // x above p
+            [&[0x02][..], &[0xff; 32]].concat(),
+        ];
+        for bytes in invalid {
+            assert!(Secp256k1Point::try_sec_parse(&bytes).is_err(), "{bytes:?}");
+        }
+    }
+
     #[test]
     fn test_point_add() {
         let prime = 223u64;
@@ -48,7 +131,7 @@ mod tests {
 
     #[test]
     fn test_scalar() {
-        let scalar_multiples = vec![
+        let scalar_multiples = [
             (47, 71),
             (36, 111),
             (15, 137),
@@ -63,7 +146,7 @@ mod tests {
         let curve = Curve::new(felt!(0, prime), felt!(7, prime));
         let generator = curve.point(felt!(47, prime), felt!(71, prime)).unwrap();
 
-        for i in 1..=scalar_multiples.len() as u32 {
+        for i in 1..=u32::try_from(scalar_multiples.len()).unwrap() {
             let result = generator.clone() * i;
             let expected = curve
                 .point(
@@ -105,7 +188,7 @@ mod tests {
         let generator = curve.point(felt!(47, prime), felt!(71, prime)).unwrap();
 
         for i in 0..10 {
-            let coefficient = (i + 1) as u32;
+            let coefficient: u32 = i + 1;
             let naive_multiple = generator.clone().naive_mul(coefficient);
             let binary_expanded = generator.clone().binary_expansion_mul(coefficient);
 
@@ -113,6 +196,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_jacobian_batch_normalize() {
+        let prime = 223u64;
+        let curve = Curve::new(felt!(0, prime), felt!(7, prime));
+        let generator = curve.point(felt!(47, prime), felt!(71, prime)).unwrap();
+        let jacobian = JacobianPoint::from(generator.clone());
+
+        // Order of the generator is 21, so the last multiples wrap around infinity
+        let multiples: Vec<JacobianPoint> = (0..25u32)
+            .map(|i| jacobian.mul(&BigUint::from(i)))
+            .collect();
+        let normalized = JacobianPoint::batch_normalize(&multiples);
+
+        for (i, (point, jacobian)) in (0..25u32).zip(normalized.iter().zip(&multiples)) {
+            let expected = generator.clone() * i;
+            assert_eq!(point, &expected);
+            assert_eq!(jacobian.to_affine(), expected);
+        }
+
+        let sum = &multiples[3] + &multiples[5];
+        assert_eq!(sum.to_affine(), generator.clone() * 8u32);
+        assert!((&multiples[7] + &multiples[14]).is_infinity());
+    }
+
+    #[test]
+    fn test_secp256k1_batch_invert() {
+        let mut elements: Vec<Secp256k1Felt> = [3u32, 0, 5, 7]
+            .into_iter()
+            .map(|i| Secp256k1Felt::new(BigUint::from(i)))
+            .collect();
+        let expected: Vec<Secp256k1Felt> = elements
+            .iter()
+            .map(|element| {
+                if element.inner() == &BigUint::from(0u32) {
+                    element.clone()
+                } else {
+                    Secp256k1Felt::new(BigUint::from(1u32)) / element
+                }
+            })
+            .collect();
+
+        Secp256k1Felt::batch_invert(&mut elements);
+        assert_eq!(elements, expected);
+    }
+
     #[test]
     fn test_secp256k1_values() {
         // The fact that this works means point is on the curve
@@ -125,7 +253,7 @@ mod tests {
                 "55066263022277343669578718895168534326250603453777594175500187360389116729240"
             )
             .unwrap(),
-            point.x.unwrap().inner().to_owned()
+            point.x.felt().unwrap().inner().to_owned()
         );
 
         assert_eq!(
@@ -133,7 +261,7 @@ mod tests {
                 "32670510020758816978083085130507043184471273380659243275938904335757337482424"
             )
             .unwrap(),
-            point.y.unwrap().inner().to_owned()
+            point.y.felt().unwrap().inner().to_owned()
         );
 
         assert_eq!(
@@ -145,6 +273,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_secp256k1_typed_field() {
+        let x = Secp256k1Fp::new(BigUint::parse_bytes(Secp256k1Point::SECP256K1_X, 16).unwrap());
+        let y = Secp256k1Fp::new(BigUint::parse_bytes(Secp256k1Point::SECP256K1_Y, 16).unwrap());
+
+        assert_eq!(y.pow(2u32), x.pow(3u32) + Secp256k1Fp::from(7));
+    }
+
     #[test]
     fn test_secp256k1_scalar() {
         let point = Secp256k1Point::g();
@@ -182,12 +318,1219 @@ mod tests {
             )
         );
 
-        assert_eq!(point.verify(&good_z, &signature), true);
+        assert!(point.verify(&good_z, &signature));
 
         let bad_z = Secp256k1Felt::from_bytes(
             &"bad000aa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60".to_bytes_be(),
         );
 
-        assert_eq!(point.verify(&bad_z, &signature), false);
+        assert!(!point.verify(&bad_z, &signature));
+    }
+
+    #[test]
+    fn test_curve_params() {
+        for params in [
+            CurveParams::secp256k1(),
+            CurveParams::secp256r1(),
+            CurveParams::toy_223(),
+            CurveParams::toy_223_order_7(),
+        ] {
+            let generator = JacobianPoint::from(params.generator.clone());
+            assert!(generator.mul(&params.order).is_infinity(), "{}", params.name);
+        }
+
+        assert_eq!(
+            Secp256k1Point::g(),
+            Secp256k1Point::new(
+                BigUint::parse_bytes(Secp256k1Point::SECP256K1_X, 16).unwrap(),
+                BigUint::parse_bytes(Secp256k1Point::SECP256K1_Y, 16).unwrap(),
+            )
+        );
+        assert_eq!(
+            Secp256k1Point::order(),
+            BigUint::parse_bytes(Secp256k1Point::SECP256K1_ORDER, 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_toy_ecdsa() {
+        // ECDSA needs a prime order, G = (47, 71) has order 21 so we use the subgroup of 7
+        let params = CurveParams::toy_223_order_7();
+        let key = SigningKey::new(params, BigUint::from(3u32)).unwrap();
+
+        for z in 0..40u32 {
+            let z = BigUint::from(z);
+            let signature = key.sign(&z);
+            assert!(key.verify(&z, &signature));
+        }
+
+        assert!(SigningKey::new(params, BigUint::from(0u32)).is_err());
+        assert!(SigningKey::new(params, BigUint::from(7u32)).is_err());
+    }
+
+    #[test]
+    fn test_generic_ecdsa_secp256k1() {
+        let secret = BigUint::from(0x0012_3456_789a_u64);
+        let wallet = PrivateKey::new(Secp256k1Felt::new(secret.clone()));
+        let key = SigningKey::new(CurveParams::secp256k1(), secret).unwrap();
+        let public_key: Point = wallet.public_key().clone().into();
+        assert_eq!(key.public_key(), &public_key);
+
+        let z = Secp256k1Felt::from_bytes(&crate::helpers::hash::hash256(b"Programming Bitcoin!"));
+        let signature = key.sign(z.inner());
+        assert!(key.verify(z.inner(), &signature));
+
+        // PrivateKey always returns the low s form
+        let n = Secp256k1Point::order();
+        let low_s = if signature.s() > &(&n >> 1) {
+            &n - signature.s()
+        } else {
+            signature.s().clone()
+        };
+        let expected = wallet.sign(&z);
+        assert_eq!(signature.r(), expected.r().inner());
+        assert_eq!(&low_s, expected.s().inner());
+
+        let tampered = EcdsaSignature::new(signature.r().clone(), signature.s() + 1u32);
+        assert!(!key.verify(z.inner(), &tampered));
+    }
+
+    fn hex(value: &str) -> BigUint {
+        BigUint::parse_bytes(value.as_bytes(), 16).unwrap()
+    }
+
+    #[test]
+    fn test_p256_rfc6979() {
+        // RFC 6979 A.2.5, ECDSA with P-256 and SHA-256
+        let key = P256PrivateKey::new(hex(
+            "C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721",
+        ))
+        .unwrap();
+
+        let expected_public_key = P256Point::new(
+            hex("60FED4BA255A9D31C961EB74C6356D68C049B8923B61FA6CE669622E60F29FB6"),
+            hex("7903FE1008B8BC99A41AE9E95628BC64F2F1B20C2D7E9F5177A3C294D4462299"),
+        )
+        .unwrap();
+        assert_eq!(key.public_key(), &expected_public_key);
+
+        let vectors = [
+            (
+                b"sample".as_slice(),
+                "EFD48B2AACB6A8FD1140DD9CD45E81D69D2C877B56AAF991C34D0EA84EAF3716",
+                "F7CB1C942D657C41D436C7A1B6E29F65F3E900DBB9AFF4064DC4AB2F843ACDA8",
+            ),
+            (
+                b"test".as_slice(),
+                "F1ABB023518351CD71D881567B1EA663ED3EFCF6C5132B354F28D3B0B7D38367",
+                "019F4113742A2B14BD25926B49C649155F267E60D3814B4C0CC84250E46F0083",
+            ),
+        ];
+
+        for (message, r, s) in vectors {
+            let signature = key.sign_slice(message);
+            assert_eq!(signature, P256Signature::new(hex(r), hex(s)));
+            assert!(key.verify_slice(message, &signature));
+        }
+    }
+
+    // NIST CAVP 186-4 SigVer.rsp, [P-256,SHA-256], with the digests of the messages
+    // extracted: public key x and y, digest, r, s and whether the result is P
+    const P256_SIGVER_VECTORS: [(&str, &str, &str, &str, &str, bool); 15] = [
+        (
+            "87f8f2b218f49845f6f10eec3877136269f5c1a54736dbdf69f89940cad41555",
+            "e15f369036f49842fac7a86c8a2b0557609776814448b8f5e84aa9f4395205e9",
+            "a82c31412f537135d1c418bd7136fb5fde9426e70c70e7c2fb11f02f30fdeae2",
+            "d19ff48b324915576416097d2544f7cbdf8768b1454ad20e0baac50e211f23b0",
+            "a3e81e59311cdfff2d4784949f7a2cb50ba6c3a91fa54710568e61aca3e847c6",
+            false,
+        ),
+        (
+            "5cf02a00d205bdfee2016f7421807fc38ae69e6b7ccd064ee689fc1a94a9f7d2",
+            "ec530ce3cc5c9d1af463f264d685afe2b4db4b5828d7e61b748930f3ce622a85",
+            "5984eab8854d0a9aa5f0c70f96deeb510e5f9ff8c51befcdc3c41bac53577f22",
+            "dc23d130c6117fb5751201455e99f36f59aba1a6a21cf2d0e7481a97451d6693",
+            "d6ce7708c18dbf35d4f8aa7240922dc6823f2e7058cbc1484fcad1599db5018c",
+            false,
+        ),
+        (
+            "2ddfd145767883ffbb0ac003ab4a44346d08fa2570b3120dcce94562422244cb",
+            "5f70c7d11ac2b7a435ccfbbae02c3df1ea6b532cc0e9db74f93fffca7c6f9a64",
+            "44b02ad3088076f997220a68ff0b27a58ecfa528b604427097cce5ca956274c5",
+            "9913111cff6f20c5bf453a99cd2c2019a4e749a49724a08774d14e4c113edda8",
+            "9467cd4cd21ecb56b0cab0a9a453b43386845459127a952421f5c6382866c5cc",
+            false,
+        ),
+        (
+            "e424dc61d4bb3cb7ef4344a7f8957a0c5134e16f7a67c074f82e6e12f49abf3c",
+            "970eed7aa2bc48651545949de1dddaf0127e5965ac85d1243d6f60e7dfaee927",
+            "d1b8ef21eb4182ee270638061063a3f3c16c114e33937f69fb232cc833965a94",
+            "bf96b99aa49c705c910be33142017c642ff540c76349b9dab72f981fd9347f4f",
+            "17c55095819089c2e03b9cd415abdf12444e323075d98f31920b9e0f57ec871c",
+            true,
+        ),
+        (
+            "e0fc6a6f50e1c57475673ee54e3a57f9a49f3328e743bf52f335e3eeaa3d2864",
+            "7f59d689c91e463607d9194d99faf316e25432870816dde63f5d4b373f12f22a",
+            "b9336a8d1f3e8ede001d19f41320bc7672d772a3d2cb0e435fff3c27d6804a2c",
+            "1d75830cd36f4c9aa181b2c4221e87f176b7f05b7c87824e82e396c88315c407",
+            "cb2acb01dac96efc53a32d4a0d85d0c2e48955214783ecf50a4f0414a319c05a",
+            true,
+        ),
+        (
+            "a849bef575cac3c6920fbce675c3b787136209f855de19ffe2e8d29b31a5ad86",
+            "bf5fe4f7858f9b805bd8dcc05ad5e7fb889de2f822f3d8b41694e6c55c16b471",
+            "640c13e290147a48c83e0ea75a0f92723cda125ee21a747e34c8d1b36f16cf2d",
+            "25acc3aa9d9e84c7abf08f73fa4195acc506491d6fc37cb9074528a7db87b9d6",
+            "9b21d5b5259ed3f2ef07dfec6cc90d3a37855d1ce122a85ba6a333f307d31537",
+            false,
+        ),
+        (
+            "3dfb6f40f2471b29b77fdccba72d37c21bba019efa40c1c8f91ec405d7dcc5df",
+            "f22f953f1e395a52ead7f3ae3fc47451b438117b1e04d613bc8555b7d6e6d1bb",
+            "8a3e7ad7b9b1b0cdc48e58d1e651fe6d710fef1420addeb61582bdd982d2b44c",
+            "548886278e5ec26bed811dbb72db1e154b6f17be70deb1b210107decb1ec2a5a",
+            "e93bfebd2f14f3d827ca32b464be6e69187f5edbd52def4f96599c37d58eee75",
+            false,
+        ),
+        (
+            "69b7667056e1e11d6caf6e45643f8b21e7a4bebda463c7fdbc13bc98efbd0214",
+            "d3f9b12eb46c7c6fda0da3fc85bc1fd831557f9abc902a3be3cb3e8be7d1aa2f",
+            "d80e9933e86769731ec16ff31e6821531bcf07fcbad9e2ac16ec9e6cb343a870",
+            "288f7a1cd391842cce21f00e6f15471c04dc182fe4b14d92dc18910879799790",
+            "247b3c4e89a3bcadfea73c7bfd361def43715fa382b8c3edf4ae15d6e55e9979",
+            false,
+        ),
+        (
+            "bf02cbcf6d8cc26e91766d8af0b164fc5968535e84c158eb3bc4e2d79c3cc682",
+            "069ba6cb06b49d60812066afa16ecf7b51352f2c03bd93ec220822b1f3dfba03",
+            "7c1048884558961c7e178b3a9b22583fca0d17f355a9887e2f96d363d2a776a3",
+            "f5acb06c59c2b4927fb852faa07faf4b1852bbb5d06840935e849c4d293d1bad",
+            "049dab79c89cc02f1484c437f523e080a75f134917fda752f2d5ca397addfe5d",
+            false,
+        ),
+        (
+            "224a4d65b958f6d6afb2904863efd2a734b31798884801fcab5a590f4d6da9de",
+            "178d51fddada62806f097aa615d33b8f2404e6b1479f5fd4859d595734d6d2b9",
+            "4c8d1afb724ad0c2ec458d866ac1dbb4497e273bbf05f88153102987e376fa75",
+            "87b93ee2fecfda54deb8dff8e426f3c72c8864991f8ec2b3205bb3b416de93d2",
+            "4044a24df85be0cc76f21a4430b75b8e77b932a87f51e4eccbc45c263ebf8f66",
+            false,
+        ),
+        (
+            "43691c7795a57ead8c5c68536fe934538d46f12889680a9cb6d055a066228369",
+            "f8790110b3c3b281aa1eae037d4f1234aff587d903d93ba3af225c27ddc9ccac",
+            "8581034ec7d7a6b163d71820923f616b362748f2846042c9896d8e4bf7577960",
+            "8acd62e8c262fa50dd9840480969f4ef70f218ebf8ef9584f199031132c6b1ce",
+            "cfca7ed3d4347fb2a29e526b43c348ae1ce6c60d44f3191b6d8ea3a2d9c92154",
+            false,
+        ),
+        (
+            "9157dbfcf8cf385f5bb1568ad5c6e2a8652ba6dfc63bc1753edf5268cb7eb596",
+            "972570f4313d47fc96f7c02d5594d77d46f91e949808825b3d31f029e8296405",
+            "e5b30e0041a33281210644938d9aaa15ef2c1247b4178f7ca1ee935ce23daabc",
+            "dfaea6f297fa320b707866125c2a7d5d515b51a503bee817de9faa343cc48eeb",
+            "8f780ad713f9c3e5a4f7fa4c519833dfefc6a7432389b1e4af463961f09764f2",
+            false,
+        ),
+        (
+            "072b10c081a4c1713a294f248aef850e297991aca47fa96a7470abe3b8acfdda",
+            "9581145cca04a0fb94cedce752c8f0370861916d2a94e7c647c5373ce6a4c8f5",
+            "edd72dc0aa91649e09e2489c37ec27efab3b61953762c6b4532a9b1cd08a500d",
+            "09f5483eccec80f9d104815a1be9cc1a8e5b12b6eb482a65c6907b7480cf4f19",
+            "a4f90e560c5e4eb8696cb276e5165b6a9d486345dedfb094a76e8442d026378d",
+            false,
+        ),
+        (
+            "09308ea5bfad6e5adf408634b3d5ce9240d35442f7fe116452aaec0d25be8c24",
+            "f40c93e023ef494b1c3079b2d10ef67f3170740495ce2cc57f8ee4b0618b8ee5",
+            "0d06ba42d256062e16b319a0f3099109518a765f26bac3b9f56930d965617726",
+            "5cc8aa7c35743ec0c23dde88dabd5e4fcd0192d2116f6926fef788cddb754e73",
+            "9c9c045ebaa1b828c32f82ace0d18daebf5e156eb7cbfdc1eff4399a8a900ae7",
+            false,
+        ),
+        (
+            "2d98ea01f754d34bbc3003df5050200abf445ec728556d7ed7d5c54c55552b6d",
+            "9b52672742d637a32add056dfd6d8792f2a33c2e69dafabea09b960bc61e230a",
+            "41007876926a20f821d72d9c6f2c9dae6c03954123ea6e6939d7e6e669438891",
+            "06108e525f845d0155bf60193222b3219c98e3d49424c2fb2a0987f825c17959",
+            "62b5cdd591e5b507e560167ba8f6f7cda74673eb315680cb89ccbc4eec477dce",
+            true,
+        ),
+    ];
+
+    // NIST CAVP 186-4 SigGen.txt, [P-256,SHA-256], with the messages hashed: secret
+    // d, public key x and y, nonce k, digest, r and s
+    const P256_SIGGEN_VECTORS: [(&str, &str, &str, &str, &str, &str, &str); 15] = [
+        (
+            "519b423d715f8b581f4fa8ee59f4771a5b44c8130b4e3eacca54a56dda72b464",
+            "1ccbe91c075fc7f4f033bfa248db8fccd3565de94bbfb12f3c59ff46c271bf83",
+            "ce4014c68811f9a21a1fdb2c0e6113e06db7ca93b7404e78dc7ccd5ca89a4ca9",
+            "94a1bbb14b906a61a280f245f9e93c7f3b4a6247824f5d33b9670787642a68de",
+            "44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56",
+            "f3ac8061b514795b8843e3d6629527ed2afd6b1f6a555a7acabb5e6f79c8c2ac",
+            "8bf77819ca05a6b2786c76262bf7371cef97b218e96f175a3ccdda2acc058903",
+        ),
+        (
+            "0f56db78ca460b055c500064824bed999a25aaf48ebb519ac201537b85479813",
+            "e266ddfdc12668db30d4ca3e8f7749432c416044f2d2b8c10bf3d4012aeffa8a",
+            "bfa86404a2e9ffe67d47c587ef7a97a7f456b863b4d02cfc6928973ab5b1cb39",
+            "6d3e71882c3b83b156bb14e0ab184aa9fb728068d3ae9fac421187ae0b2f34c6",
+            "9b2db89cb0e8fa3cc7608b4d6cc1dec0114e0b9ff4080bea12b134f489ab2bbc",
+            "976d3a4e9d23326dc0baa9fa560b7c4e53f42864f508483a6473b6a11079b2db",
+            "1b766e9ceb71ba6c01dcd46e0af462cd4cfa652ae5017d4555b8eeefe36e1932",
+        ),
+        (
+            "e283871239837e13b95f789e6e1af63bf61c918c992e62bca040d64cad1fc2ef",
+            "74ccd8a62fba0e667c50929a53f78c21b8ff0c3c737b0b40b1750b2302b0bde8",
+            "29074e21f3a0ef88b9efdf10d06aa4c295cc1671f758ca0e4cd108803d0f2614",
+            "ad5e887eb2b380b8d8280ad6e5ff8a60f4d26243e0124c2f31a297b5d0835de2",
+            "b804cf88af0c2eff8bbbfb3660ebb3294138e9d3ebd458884e19818061dacff0",
+            "35fb60f5ca0f3ca08542fb3cc641c8263a2cab7a90ee6a5e1583fac2bb6f6bd1",
+            "ee59d81bc9db1055cc0ed97b159d8784af04e98511d0a9a407b99bb292572e96",
+        ),
+        (
+            "a3d2d3b7596f6592ce98b4bfe10d41837f10027a90d7bb75349490018cf72d07",
+            "322f80371bf6e044bc49391d97c1714ab87f990b949bc178cb7c43b7c22d89e1",
+            "3c15d54a5cc6b9f09de8457e873eb3deb1fceb54b0b295da6050294fae7fd999",
+            "24fc90e1da13f17ef9fe84cc96b9471ed1aaac17e3a4bae33a115df4e5834f18",
+            "85b957d92766235e7c880ac5447cfbe97f3cb499f486d1e43bcb5c2ff9608a1a",
+            "d7c562370af617b581c84a2468cc8bd50bb1cbf322de41b7887ce07c0e5884ca",
+            "b46d9f2d8c4bf83546ff178f1d78937c008d64e8ecc5cbb825cb21d94d670d89",
+        ),
+        (
+            "53a0e8a8fe93db01e7ae94e1a9882a102ebd079b3a535827d583626c272d280d",
+            "1bcec4570e1ec2436596b8ded58f60c3b1ebc6a403bc5543040ba82963057244",
+            "8af62a4c683f096b28558320737bf83b9959a46ad2521004ef74cf85e67494e1",
+            "5d833e8d24cc7a402d7ee7ec852a3587cddeb48358cea71b0bedb8fabe84e0c4",
+            "3360d699222f21840827cf698d7cb635bee57dc80cd7733b682d41b55b666e22",
+            "18caaf7b663507a8bcd992b836dec9dc5703c080af5e51dfa3a9a7c387182604",
+            "77c68928ac3b88d985fb43fb615fb7ff45c18ba5c81af796c613dfa98352d29c",
+        ),
+        (
+            "4af107e8e2194c830ffb712a65511bc9186a133007855b49ab4b3833aefc4a1d",
+            "a32e50be3dae2c8ba3f5e4bdae14cf7645420d425ead94036c22dd6c4fc59e00",
+            "d623bf641160c289d6742c6257ae6ba574446dd1d0e74db3aaa80900b78d4ae9",
+            "e18f96f84dfa2fd3cdfaec9159d4c338cd54ad314134f0b31e20591fc238d0ab",
+            "c413c4908cd0bc6d8e32001aa103043b2cf5be7fcbd61a5cec9488c3a577ca57",
+            "8524c5024e2d9a73bde8c72d9129f57873bbad0ed05215a372a84fdbc78f2e68",
+            "d18c2caf3b1072f87064ec5e8953f51301cada03469c640244760328eb5a05cb",
+        ),
+        (
+            "78dfaa09f1076850b3e206e477494cddcfb822aaa0128475053592c48ebaf4ab",
+            "8bcfe2a721ca6d753968f564ec4315be4857e28bef1908f61a366b1f03c97479",
+            "0f67576a30b8e20d4232d8530b52fb4c89cbc589ede291e499ddd15fe870ab96",
+            "295544dbb2da3da170741c9b2c6551d40af7ed4e891445f11a02b66a5c258a77",
+            "88fc1e7d849794fc51b135fa135deec0db02b86c3cd8cebdaa79e8689e5b2898",
+            "c5a186d72df452015480f7f338970bfe825087f05c0088d95305f87aacc9b254",
+            "84a58f9e9d9e735344b316b1aa1ab5185665b85147dc82d92e969d7bee31ca30",
+        ),
+        (
+            "80e692e3eb9fcd8c7d44e7de9f7a5952686407f90025a1d87e52c7096a62618a",
+            "a88bc8430279c8c0400a77d751f26c0abc93e5de4ad9a4166357952fe041e767",
+            "2d365a1eef25ead579cc9a069b6abc1b16b81c35f18785ce26a10ba6d1381185",
+            "7c80fd66d62cc076cef2d030c17c0a69c99611549cb32c4ff662475adbe84b22",
+            "41fa8d8b4cd0a5fdf021f4e4829d6d1e996bab6b4a19dcb85585fe76c582d2bc",
+            "9d0c6afb6df3bced455b459cc21387e14929392664bb8741a3693a1795ca6902",
+            "d7f9ddd191f1f412869429209ee3814c75c72fa46a9cccf804a2f5cc0b7e739f",
+        ),
+        (
+            "5e666c0db0214c3b627a8e48541cc84a8b6fd15f300da4dff5d18aec6c55b881",
+            "1bc487570f040dc94196c9befe8ab2b6de77208b1f38bdaae28f9645c4d2bc3a",
+            "ec81602abd8345e71867c8210313737865b8aa186851e1b48eaca140320f5d8f",
+            "2e7625a48874d86c9e467f890aaa7cd6ebdf71c0102bfdcfa24565d6af3fdce9",
+            "2d72947c1731543b3d62490866a893952736757746d9bae13e719079299ae192",
+            "2f9e2b4e9f747c657f705bffd124ee178bbc5391c86d056717b140c153570fd9",
+            "f5413bfd85949da8d83de83ab0d19b2986613e224d1901d76919de23ccd03199",
+        ),
+        (
+            "f73f455271c877c4d5334627e37c278f68d143014b0a05aa62f308b2101c5308",
+            "b8188bd68701fc396dab53125d4d28ea33a91daf6d21485f4770f6ea8c565dde",
+            "423f058810f277f8fe076f6db56e9285a1bf2c2a1dae145095edd9c04970bc4a",
+            "62f8665fd6e26b3fa069e85281777a9b1f0dfd2c0b9f54a086d0c109ff9fd615",
+            "e138bd577c3729d0e24a98a82478bcc7482499c4cdf734a874f7208ddbc3c116",
+            "1cc628533d0004b2b20e7f4baad0b8bb5e0673db159bbccf92491aef61fc9620",
+            "880e0bbf82a8cf818ed46ba03cf0fc6c898e36fca36cc7fdb1d2db7503634430",
+        ),
+        (
+            "b20d705d9bd7c2b8dc60393a5357f632990e599a0975573ac67fd89b49187906",
+            "51f99d2d52d4a6e734484a018b7ca2f895c2929b6754a3a03224d07ae61166ce",
+            "4737da963c6ef7247fb88d19f9b0c667cac7fe12837fdab88c66f10d3c14cad1",
+            "72b656f6b35b9ccbc712c9f1f3b1a14cbbebaec41c4bca8da18f492a062d6f6f",
+            "17b03f9f00f6692ccdde485fc63c4530751ef35da6f71336610944b0894fcfb8",
+            "9886ae46c1415c3bc959e82b760ad760aab66885a84e620aa339fdf102465c42",
+            "2bf3a80bc04faa35ebecc0f4864ac02d349f6f126e0f988501b8d3075409a26c",
+        ),
+        (
+            "d4234bebfbc821050341a37e1240efe5e33763cbbb2ef76a1c79e24724e5a5e7",
+            "8fb287f0202ad57ae841aea35f29b2e1d53e196d0ddd9aec24813d64c0922fb7",
+            "1f6daff1aa2dd2d6d3741623eecb5e7b612997a1039aab2e5cf2de969cfea573",
+            "d926fe10f1bfd9855610f4f5a3d666b1a149344057e35537373372ead8b1a778",
+            "c25beae638ff8dcd370e03a6f89c594c55bed1277ee14d83bbb0ef783a0517c7",
+            "490efd106be11fc365c7467eb89b8d39e15d65175356775deab211163c2504cb",
+            "644300fc0da4d40fb8c6ead510d14f0bd4e1321a469e9c0a581464c7186b7aa7",
+        ),
+        (
+            "b58f5211dff440626bb56d0ad483193d606cf21f36d9830543327292f4d25d8c",
+            "68229b48c2fe19d3db034e4c15077eb7471a66031f28a980821873915298ba76",
+            "303e8ee3742a893f78b810991da697083dd8f11128c47651c27a56740a80c24c",
+            "e158bf4a2d19a99149d9cdb879294ccb7aaeae03d75ddd616ef8ae51a6dc1071",
+            "5eb28029ebf3c7025ff2fc2f6de6f62aecf6a72139e1cba5f20d11bbef036a7f",
+            "e67a9717ccf96841489d6541f4f6adb12d17b59a6bef847b6183b8fcf16a32eb",
+            "9ae6ba6d637706849a6a9fc388cf0232d85c26ea0d1fe7437adb48de58364333",
+        ),
+        (
+            "54c066711cdb061eda07e5275f7e95a9962c6764b84f6f1f3ab5a588e0a2afb1",
+            "0a7dbb8bf50cb605eb2268b081f26d6b08e012f952c4b70a5a1e6e7d46af98bb",
+            "f26dd7d799930062480849962ccf5004edcfd307c044f4e8f667c9baa834eeae",
+            "646fe933e96c3b8f9f507498e907fdd201f08478d0202c752a7c2cfebf4d061a",
+            "12135386c09e0bf6fd5c454a95bcfe9b3edb25c71e455c73a212405694b29002",
+            "b53ce4da1aa7c0dc77a1896ab716b921499aed78df725b1504aba1597ba0c64b",
+            "d7c246dc7ad0e67700c373edcfdd1c0a0495fc954549ad579df6ed1438840851",
+        ),
+        (
+            "34fa4682bf6cb5b16783adcd18f0e6879b92185f76d7c920409f904f522db4b1",
+            "105d22d9c626520faca13e7ced382dcbe93498315f00cc0ac39c4821d0d73737",
+            "6c47f3cbbfa97dfcebe16270b8c7d5d3a5900b888c42520d751e8faf3b401ef4",
+            "a6f463ee72c9492bc792fe98163112837aebd07bab7a84aaed05be64db3086f4",
+            "aea3e069e03c0ff4d6b3fa2235e0053bbedc4c7e40efbc686d4dfb5efba4cfed",
+            "542c40a18140a6266d6f0286e24e9a7bad7650e72ef0e2131e629c076d962663",
+            "4f7f65305e24a6bbb5cff714ba8f5a2cee5bdc89ba8d75dcbf21966ce38eb66f",
+        ),
+    ];
+
+    #[test]
+    fn test_p256_sigver() {
+        for (x, y, digest, r, s, valid) in P256_SIGVER_VECTORS {
+            let public_key = P256Point::new(hex(x), hex(y)).unwrap();
+            let signature = P256Signature::new(hex(r), hex(s));
+            assert_eq!(public_key.verify(&hex(digest), &signature), valid, "{digest}");
+        }
+    }
+
+    #[test]
+    fn test_p256_siggen() {
+        for (d, x, y, k, digest, r, s) in P256_SIGGEN_VECTORS {
+            let key = SigningKey::new(P256Point::params(), hex(d)).unwrap();
+            let public_key = P256Point::new(hex(x), hex(y)).unwrap();
+            assert_eq!(key.public_key(), &Point::from(public_key.clone()));
+
+            let signature = key.sign_with_k(&hex(digest), &hex(k)).unwrap();
+            assert_eq!(signature, P256Signature::new(hex(r), hex(s)));
+            assert!(public_key.verify(&hex(digest), &signature));
+        }
+    }
+
+    #[test]
+    fn test_p256_sec() {
+        let point = P256Point::g() * BigUint::from(0xdead_beef_u32);
+
+        let compressed = point.sec_compressed().unwrap();
+        let uncompressed = point.sec_uncompressed().unwrap();
+        assert_eq!(compressed.len(), 33);
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(P256Point::sec_parse(&compressed).unwrap(), point);
+        assert_eq!(P256Point::sec_parse(&uncompressed).unwrap(), point);
+
+        assert!(P256Point::sec_parse(&compressed[..32]).is_err());
+        assert!(P256Point::sec_parse(&[0x05; 33]).is_err());
+
+        let mut off_curve = uncompressed;
+        off_curve[64] ^= 1;
+        assert!(P256Point::sec_parse(&off_curve).is_err());
+    }
+
+    #[test]
+    fn test_dlog_toy_curve() {
+        // Order 21 = 3 * 7 exercises Pohlig-Hellman and composite order rho
+        for params in [CurveParams::toy_223(), CurveParams::toy_223_order_7()] {
+            let generator = &params.generator;
+            for k in 0..params.order.to_u64_digits().first().copied().unwrap() {
+                let k = BigUint::from(k);
+                let q = generator.clone() * k.clone();
+
+                for solver in [baby_step_giant_step, pollard_rho, pohlig_hellman] {
+                    let dlog = solver(generator, &q, &params.order).unwrap();
+                    assert_eq!(dlog.k, k, "{}", params.name);
+                    assert!(dlog.group_operations > 0 || dlog.k == BigUint::from(0u32));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dlog_not_a_multiple() {
+        // (15, 86) generates a subgroup of 7 points that doesn't contain (47, 71)
+        let p = &CurveParams::toy_223_order_7().generator;
+        let q = &CurveParams::toy_223().generator;
+        let order = BigUint::from(7u32);
+
+        assert!(baby_step_giant_step(p, q, &order).is_err());
+        assert!(pollard_rho(p, q, &order).is_err());
+        assert!(pohlig_hellman(p, q, &order).is_err());
+    }
+
+    #[test]
+    fn test_dlog_degenerate_order() {
+        let p = &CurveParams::toy_223().generator;
+
+        for order in [0u32, 1] {
+            let order = BigUint::from(order);
+            assert!(baby_step_giant_step(p, p, &order).is_err());
+            assert!(pollard_rho(p, p, &order).is_err());
+            assert!(pohlig_hellman(p, p, &order).is_err());
+        }
+    }
+
+    #[test]
+    fn test_dlog_secp256k1_out_of_reach() {
+        let g: Point = Secp256k1Point::g().into();
+        let q: Point = (Secp256k1Point::g() * 5u32).into();
+        let order = Secp256k1Point::order();
+
+        assert_eq!(expected_work(&order).bits(), 128);
+
+        let error = baby_step_giant_step(&g, &q, &order).unwrap_err();
+        assert!(error.to_string().contains("2^128"));
+        assert!(pollard_rho(&g, &q, &order).is_err());
+    }
+
+    #[test]
+    fn test_count_points() {
+        let prime = 223u64;
+        let curve = Curve::new(felt!(0, prime), felt!(7, prime));
+        assert_eq!(curve.count_points().unwrap(), BigUint::from(252u32));
+
+        // Both methods agree on curves above the naive threshold
+        for (prime, a, b) in [(10007u64, 2u64, 3u64), (4507, 0, 7), (5003, 5, 11)] {
+            let curve = Curve::new(felt!(a, prime), felt!(b, prime));
+            assert_eq!(
+                curve.count_points().unwrap(),
+                curve.count_points_naive().unwrap(),
+                "{prime}"
+            );
+
+            let twist_points = curve.twist().count_points_naive().unwrap();
+            assert_eq!(
+                curve.count_points().unwrap() + twist_points,
+                BigUint::from(2 * prime + 2)
+            );
+        }
+
+        assert!(Secp256k1Point::curve().count_points().is_err());
+    }
+
+    #[test]
+    fn test_point_order() {
+        let prime = 223u64;
+        let curve = Curve::new(felt!(0, prime), felt!(7, prime));
+
+        let point = curve.point(felt!(47, prime), felt!(71, prime)).unwrap();
+        assert_eq!(point.order().unwrap(), BigUint::from(21u32));
+        assert_eq!(point.cofactor().unwrap(), BigUint::from(12u32));
+        assert_eq!(point.subgroup().unwrap().len(), 21);
+
+        let point = curve.point(felt!(15, prime), felt!(86, prime)).unwrap();
+        assert_eq!(point.order().unwrap(), BigUint::from(7u32));
+        assert_eq!(point.cofactor().unwrap(), BigUint::from(36u32));
+        assert_eq!(point.subgroup().unwrap()[1], point);
+        assert!(point.order_in(&BigUint::from(10u32)).is_err());
+
+        assert_eq!(curve.identity().order().unwrap(), BigUint::from(1u32));
+
+        // Point of order 8011, above the limit of enumerated subgroups
+        let prime = 8191u64;
+        let curve = Curve::new(felt!(0, prime), felt!(7, prime));
+        let point = curve.point(felt!(1, prime), felt!(256, prime)).unwrap();
+        assert!(point.subgroup().is_err());
+    }
+
+    #[test]
+    fn test_curve_analysis() {
+        let prime = 223u64;
+        let curve = Curve::new(felt!(0, prime), felt!(7, prime));
+        assert_eq!(
+            curve.analyze().unwrap(),
+            CurveAnalysis {
+                singular: false,
+                points: Some(BigUint::from(252u32)),
+                anomalous: false,
+                factors: vec![(2, 2), (3, 2), (7, 1)],
+            }
+        );
+
+        // y^2 = x^3 + x + 14 over F_43 has exactly 43 points
+        let curve = Curve::new(felt!(1, 43), felt!(14, 43));
+        assert!(curve.is_anomalous().unwrap());
+        assert!(curve.analyze().unwrap().anomalous);
+
+        // y^2 = x^3 - 3x + 2 = (x - 1)^2 (x + 2) has a node
+        let curve = Curve::new(felt!(220, prime), felt!(2, prime));
+        assert!(curve.is_singular());
+        assert!(curve.count_points().is_err());
+        assert!(curve.analyze().unwrap().singular);
+    }
+
+    #[test]
+    fn test_point_neg_sub() {
+        let prime = 223u64;
+        let curve = Curve::new(felt!(0, prime), felt!(7, prime));
+        let generator = curve.point(felt!(47, prime), felt!(71, prime)).unwrap();
+
+        let negated = -&generator;
+        assert_eq!(negated, curve.point(felt!(47, prime), felt!(152, prime)).unwrap());
+        assert_eq!(negated, generator.clone() * 20u32);
+        assert!((&generator + &negated).is_infinity());
+        assert_eq!(-curve.identity(), curve.identity());
+
+        assert_eq!(generator.clone() * 7u32 - generator.clone() * 3u32, generator.clone() * 4u32);
+        assert_eq!(&generator - &generator, curve.identity());
+        assert_eq!(generator.double(), generator.clone() + generator.clone());
+        assert_eq!(generator.coordinates(), Some((&felt!(47, prime), &felt!(71, prime))));
+        assert_eq!(curve.identity().coordinates(), None);
+        assert_eq!(curve.identity().x.felt(), None);
+    }
+
+    #[test]
+    fn test_secp256k1_neg_sub() {
+        let g = Secp256k1Point::g();
+        let n_minus_one = Secp256k1Point::order() - 1u32;
+
+        assert_eq!(-&g, g.clone() * n_minus_one);
+        assert!((&g - &g).is_infinity());
+        assert_eq!(g.clone() * 5u32 - g.clone() * 2u32, g.clone() * 3u32);
+        assert_eq!(g.double(), g.clone() * 2u32);
+        assert!(!g.is_infinity());
+    }
+
+    fn toy_msm_terms(count: u32) -> Vec<(BigUint, Point)> {
+        let generator = CurveParams::toy_223().generator.clone();
+        (0..count)
+            .map(|i| (BigUint::from(i * 37 + 11), generator.clone() * (i % 20 + 1)))
+            .collect()
+    }
+
+    #[test]
+    fn test_multi_scalar_mul() {
+        assert_eq!(Point::multi_scalar_mul(&[]), None);
+
+        for count in [1, 2, 5, 40] {
+            let terms = toy_msm_terms(count);
+            let expected = terms
+                .iter()
+                .fold(CurveParams::toy_223().curve.identity(), |sum, (k, p)| {
+                    sum + p.clone() * k.clone()
+                });
+
+            assert_eq!(Point::multi_scalar_mul_naive(&terms).unwrap(), expected);
+            assert_eq!(Point::straus(&terms).unwrap(), expected);
+            assert_eq!(Point::pippenger(&terms).unwrap(), expected);
+            assert_eq!(Point::multi_scalar_mul(&terms).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_multi_scalar_mul() {
+        let g = Secp256k1Point::g();
+        let terms = vec![
+            (BigUint::from(3u32), g.clone()),
+            (BigUint::from(5u32), g.clone() * 7u32),
+            (Secp256k1Point::order() + 2u32, g.clone() * 11u32),
+        ];
+
+        // 3 + 35 + 22 = 60
+        assert_eq!(Secp256k1Point::multi_scalar_mul(&terms), g.clone() * 60u32);
+        assert!(Secp256k1Point::multi_scalar_mul(&[]).is_infinity());
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with `cargo test --release -- --ignored --nocapture`"]
+    fn bench_multi_scalar_mul() {
+        let g = Secp256k1Point::g();
+        for count in [4u32, 16, 64] {
+            let terms: Vec<(BigUint, Point)> = (1..=count)
+                .map(|i| {
+                    let scalar = Secp256k1Point::order() / BigUint::from(i * 7 + 3);
+                    (scalar, (g.clone() * i).into())
+                })
+                .collect();
+
+            let start = std::time::Instant::now();
+            let naive = Point::multi_scalar_mul_naive(&terms);
+            let naive_time = start.elapsed();
+
+            let start = std::time::Instant::now();
+            let straus = Point::straus(&terms);
+            let straus_time = start.elapsed();
+
+            let start = std::time::Instant::now();
+            let pippenger = Point::pippenger(&terms);
+            let pippenger_time = start.elapsed();
+
+            assert_eq!(naive, straus);
+            assert_eq!(naive, pippenger);
+            println!(
+                "{count} terms: naive {naive_time:?}, straus {straus_time:?}, pippenger {pippenger_time:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // RFC 9380 appendix K.1, expand_message_xmd(SHA-256)
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+
+        let uniform = hash_to_curve::expand_message_xmd(b"", dst, 0x20).unwrap();
+        assert_eq!(
+            uniform,
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235".to_bytes_be()
+        );
+
+        let uniform = hash_to_curve::expand_message_xmd(b"abc", dst, 0x20).unwrap();
+        assert_eq!(
+            uniform,
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615".to_bytes_be()
+        );
+
+        assert_eq!(
+            hash_to_curve::expand_message_xmd(b"abc", dst, 0x80).unwrap().len(),
+            0x80
+        );
+        assert!(hash_to_curve::expand_message_xmd(b"abc", dst, 256 * 32).is_err());
+    }
+
+    #[test]
+    fn test_hash_to_curve() {
+        // RFC 9380 appendix J.8.1, secp256k1_XMD:SHA-256_SSWU_RO_
+        let dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+
+        let u = hash_to_curve::hash_to_field(b"", dst, 2).unwrap();
+        assert_eq!(
+            u[0].inner(),
+            &hex("6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3")
+        );
+        assert_eq!(
+            u[1].inner(),
+            &hex("1ae6c212e08fe1a5937f6202f929a2cc8ef4ee5b9782db68b0d5799fd8f09e16")
+        );
+
+        let vectors = [
+            (
+                &b""[..],
+                "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+                "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+            ),
+            (
+                b"abc",
+                "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+                "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+            ),
+        ];
+        for (message, x, y) in vectors {
+            assert_eq!(
+                Secp256k1Point::hash_to_curve(message, dst).unwrap(),
+                Secp256k1Point::new(hex(x), hex(y))
+            );
+        }
+
+        // RFC 9380 appendix J.8.2, secp256k1_XMD:SHA-256_SSWU_NU_
+        let dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_NU_";
+        assert_eq!(
+            Secp256k1Point::encode_to_curve(b"", dst).unwrap(),
+            Secp256k1Point::new(
+                hex("a4792346075feae77ac3b30026f99c1441b4ecf666ded19b7522cf65c4c55c5b"),
+                hex("62c59e2a6aeed1b23be5883e833912b08ba06be7f57c0e9cdc663f31639ff3a7")
+            )
+        );
+
+        // Mapped points land on secp256k1
+        let curve = Secp256k1Point::curve();
+        for u in hash_to_curve::hash_to_field(b"on curve", dst, 8).unwrap() {
+            let point = hash_to_curve::map_to_curve(&u);
+            let (x, y) = point.coordinates().unwrap();
+            assert!(curve.point(x.clone(), y.clone()).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_try_and_increment() {
+        // (message, counter of the first valid candidate, x), x = SHA256(message ||
+        // counter) with the counter as 4 big-endian bytes, computed independently
+        let vectors = [
+            (
+                b"message".as_slice(),
+                0u32,
+                "d5453e005c9d34983e77e146c790d16e43058ecad4889043656e0d731fa0d06a",
+            ),
+            (
+                b"other message".as_slice(),
+                4,
+                "16495e80c4f448cc3403ca0e6c7da73b9aa6bbd47df5eb5a3e9bcd0671d35ec4",
+            ),
+        ];
+
+        for (message, counter, x) in vectors {
+            let point = Secp256k1Point::try_and_increment(message);
+            let candidate = Sha256::new()
+                .chain_update(message)
+                .chain_update(counter.to_be_bytes())
+                .finalize();
+            assert_eq!(point.x().felt().unwrap().inner(), &hex(x));
+            assert_eq!(hex(x), BigUint::from_bytes_be(&candidate));
+            assert!(!point.y().felt().unwrap().inner().bit(0));
+        }
+    }
+
+    /// Signs z with the given k, normalizing to low-S like `PrivateKey::sign`
+    #[allow(clippy::many_single_char_names)]
+    fn sign_with_nonce(secret: u32, k: u32, z: u32) -> nonce_reuse::SignedHash {
+        let n = Secp256k1Point::order();
+        let secret = Secp256k1Felt::new(BigUint::from(secret));
+        let z = Secp256k1Felt::new(BigUint::from(z));
+
+        let r = Secp256k1Felt::from(
+            (Secp256k1Point::g() * k)
+                .x()
+                .felt()
+                .cloned()
+                .unwrap(),
+        );
+        let mut s = (&z + &r * &secret) / Secp256k1Felt::new(BigUint::from(k));
+        if s.inner() > &(&n / 2u32) {
+            s = Secp256k1Felt::new(&n - s.inner());
+        }
+
+        let public_key = Secp256k1Point::g() * secret.inner();
+        (z, Signature::new(r, s), public_key)
+    }
+
+    #[test]
+    fn test_nonce_reuse() {
+        let signatures = vec![
+            sign_with_nonce(12345, 777, 1),
+            sign_with_nonce(999, 4242, 2),
+            sign_with_nonce(12345, 777, 3),
+            sign_with_nonce(12345, 778, 4),
+            // A different key reusing the same nonce leaks as well
+            sign_with_nonce(54321, 777, 5),
+        ];
+        for (z, signature, public_key) in &signatures {
+            assert!(signature.verify(z, public_key));
+        }
+
+        let report = nonce_reuse::analyze(&signatures);
+        assert_eq!(report.repeated.len(), 1);
+        assert_eq!(report.repeated[0].indices, vec![0, 2, 4]);
+
+        assert_eq!(report.recovered.len(), 2);
+        assert_eq!(report.recovered[0].secret, BigUint::from(12345u32));
+        assert_eq!(report.recovered[0].indices, vec![0, 2]);
+        assert_eq!(report.recovered[1].secret, BigUint::from(54321u32));
+        assert_eq!(report.recovered[1].indices, vec![4]);
+
+        let k = &report.recovered[0].k;
+        assert!(k == &BigUint::from(777u32) || k == &(Secp256k1Point::order() - 777u32));
+
+        let private_key = report.recovered[0].private_key();
+        assert_eq!(private_key.public_key(), &signatures[0].2);
+
+        // Distinct nonces reveal nothing
+        let report = nonce_reuse::analyze(&signatures[1..4]);
+        assert!(report.repeated.is_empty());
+        assert!(report.recovered.is_empty());
+
+        // Same r under a wrong public key is reported but not recovered
+        let mut forged = vec![signatures[0].clone(), signatures[2].clone()];
+        forged[1].2 = Secp256k1Point::g();
+        let report = nonce_reuse::analyze(&forged);
+        assert_eq!(report.repeated.len(), 1);
+        assert!(report.recovered.is_empty());
+    }
+
+    /// r of the RFC 6979 A.2.5 signature, the P-256 key signing the message hashed with D
+    fn p256_rfc6979_r<D: Digest + BlockSizeUser>(
+        message: &[u8],
+    ) -> BigUint {
+        let secret = hex("C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721");
+        let order = P256Point::order();
+        let k = rfc6979::Rfc6979::<D>::new(&order, &secret, &D::digest(message), None)
+            .next()
+            .unwrap();
+
+        (P256Point::g() * k).x().felt().unwrap().inner() % &order
+    }
+
+    #[test]
+    fn test_rfc6979_nonces() {
+        // RFC 6979 A.1.2, qlen = 163 is not a multiple of 8 and h1 is longer than it
+        let order = hex("04000000000000000000020108A2E0CC0D99F8A5EF");
+        let secret = hex("009A4D6792295A7F730FC3F2B49CBC0F62E862272F");
+        let hash = Sha256::digest(b"sample");
+        let mut nonces = rfc6979::Rfc6979::<Sha256>::new(&order, &secret, &hash, None);
+        assert_eq!(
+            nonces.next().unwrap(),
+            hex("023AF4074C90A02B3FE61D286D5C87F425E6BDD81B")
+        );
+
+        // RFC 6979 A.2.5, P-256 with "sample" and SHA-256
+        let secret = hex("C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721");
+        let hash = Sha256::digest(b"sample");
+        let k = rfc6979::Rfc6979::<Sha256>::new(&P256Point::order(), &secret, &hash, None)
+            .next()
+            .unwrap();
+        assert_eq!(
+            k,
+            hex("A6E3C57DD01ABE90086538398355DD4C3B17AA873382B0F24D6129493D8AAD60")
+        );
+
+        // RFC 6979 A.2.5, r of the signatures with the other hash functions
+        let vectors = [
+            (
+                p256_rfc6979_r::<Sha224>(b"sample"),
+                "53b2fff5d1752b2c689df257c04c40a587fababb3f6fc2702f1343af7ca9aa3f",
+            ),
+            (
+                p256_rfc6979_r::<Sha256>(b"sample"),
+                "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716",
+            ),
+            (
+                p256_rfc6979_r::<Sha384>(b"sample"),
+                "0eafea039b20e9b42309fb1d89e213057cbf973dc0cfc8f129edddc800ef7719",
+            ),
+            (
+                p256_rfc6979_r::<Sha512>(b"sample"),
+                "8496a60b5e9b47c825488827e0495b0e3fa109ec4568fd3f8d1097678eb97f00",
+            ),
+            (
+                p256_rfc6979_r::<Sha224>(b"test"),
+                "c37edb6f0ae79d47c3c27e962fa269bb4f441770357e114ee511f662ec34a692",
+            ),
+            (
+                p256_rfc6979_r::<Sha256>(b"test"),
+                "f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367",
+            ),
+            (
+                p256_rfc6979_r::<Sha384>(b"test"),
+                "83910e8b48bb0c74244ebdf7f07a1c5413d61472bd941ef3920e623fbccebeb6",
+            ),
+            (
+                p256_rfc6979_r::<Sha512>(b"test"),
+                "461d93f31b6540894788fd206c07cfa0cc35f46fa3c91816fff1040ad1581a04",
+            ),
+        ];
+        for (r, expected) in vectors {
+            assert_eq!(r, hex(expected));
+        }
+    }
+
+    #[test]
+    fn test_rfc6979_extra_entropy() {
+        let order = Secp256k1Point::order();
+        let secret = BigUint::from(12345u32);
+        let z = BigUint::from(67890u32);
+        let nonce = |extra: Option<&[u8]>| {
+            rfc6979::Rfc6979::<Sha256>::from_z(&order, &secret, &z, extra)
+                .next()
+                .unwrap()
+        };
+
+        // Empty additional data is the same as none
+        assert_eq!(nonce(None), nonce(Some(&[])));
+        assert_ne!(nonce(None), nonce(Some(&[1; 32])));
+        assert_ne!(nonce(Some(&[1; 32])), nonce(Some(&[2; 32])));
+        assert_eq!(nonce(Some(&[1; 32])), nonce(Some(&[1; 32])));
+
+        let private_key = PrivateKey::new(Secp256k1Felt::new(secret.clone()));
+        let z = Secp256k1Felt::new(z);
+        let signature = private_key.sign_with_extra_entropy(&z, &[1; 32]);
+        assert!(private_key.verify(&z, &signature));
+        assert_ne!(signature, private_key.sign(&z));
+    }
+
+    #[test]
+    fn test_rfc6979_z_reduction() {
+        // z = n is reduced to 0 rather than encoded as is
+        let private_key = PrivateKey::new(Secp256k1Felt::new(BigUint::from(12345u32)));
+        let zero = Secp256k1Felt::new(BigUint::from(0u32));
+        let order = Secp256k1Felt::new(Secp256k1Point::order());
+
+        assert_eq!(private_key.sign(&order), private_key.sign(&zero));
+    }
+
+    #[test]
+    fn test_sign_low_r() {
+        let private_key = PrivateKey::new(Secp256k1Felt::new(BigUint::from(12345u32)));
+        let mut ground = 0;
+
+        for message in 0u32..6 {
+            let z = Secp256k1Felt::new(BigUint::from(message));
+            let plain = private_key.sign(&z);
+            let signature = private_key.sign_low_r(&z);
+
+            assert!(signature.has_low_r());
+            assert!(private_key.verify(&z, &signature));
+
+            if plain.has_low_r() {
+                assert_eq!(signature, plain);
+            } else {
+                // The first counter Bitcoin Core feeds as extra entropy that gives a low r
+                ground += 1;
+                let expected = (1u32..64)
+                    .map(|counter| {
+                        let mut extra_entropy = [0u8; 32];
+                        extra_entropy[..4].copy_from_slice(&counter.to_le_bytes());
+                        private_key.sign_with_extra_entropy(&z, &extra_entropy)
+                    })
+                    .find(Signature::has_low_r)
+                    .unwrap();
+                assert_eq!(signature, expected);
+            }
+        }
+
+        // About half of the plain signatures have a high r
+        assert!(ground > 0);
+
+        // Keys strSecret1 and strSecret2 of Bitcoin Core's key_tests.cpp, with its
+        // signatures of "Very deterministic message", then messages of its
+        // key_signature_tests that are ground, "A message to be signed0" with a counter
+        // of 2 and "A message to be signed3" with 4. Their DER is what CKey::Sign
+        // returns with Core's libsecp256k1
+        let vectors = [
+            (
+                "12b004fff7f4b69ef8650e767f18f11ede158148b425660723b9f9a66e61f747",
+                b"Very deterministic message".as_slice(),
+                "304402205dbbddda71772d95ce91cd2d14b592cfbc1dd0aabd6a394b6c2d377bbe59d31d022014ddda21494a4e221f0824f0b8b924c43fa43c0ad57dccdaa11f81a6bd4582f6",
+            ),
+            (
+                "b524c28b61c9b2c49b2c7dd4c2d75887abb78768c054bd7c01af4029f6c0d117",
+                b"Very deterministic message".as_slice(),
+                "3044022052d8a32079c11e79db95af63bb9600c5b04f21a9ca33dc129c2bfa8ac9dc1cd5022061d8ae5e0f6c1a16bde3719c64c2fd70e404b6428ab9a69566962e8771b5944d",
+            ),
+            (
+                "12b004fff7f4b69ef8650e767f18f11ede158148b425660723b9f9a66e61f747",
+                b"A message to be signed0".as_slice(),
+                "3044022068663052e6c29c7ed7ab02a68852301508503e7986b9754ec3e868772f2bf739022028c6a35b2e90250d3179f96c2bb6b772e889e9a133a5156564a6965a8caa2b26",
+            ),
+            (
+                "12b004fff7f4b69ef8650e767f18f11ede158148b425660723b9f9a66e61f747",
+                b"A message to be signed3".as_slice(),
+                "304402202a2ef72332751ba93836e209f4bf049870cd93c9cf9fa40e2f5d3791f33e312d0220393458d7b9013524f21d7f979ada879dfd00839a03ea20905f0bbd8390c3a21b",
+            ),
+        ];
+
+        for (secret, message, der) in vectors {
+            let private_key = PrivateKey::new(Secp256k1Felt::new(hex(secret)));
+            let z = Secp256k1Felt::from_bytes(&crate::helpers::hash::hash256(message));
+            assert_eq!(private_key.sign_low_r(&z).der(), der.to_bytes_be());
+        }
+    }
+
+    #[test]
+    #[allow(clippy::many_single_char_names)]
+    fn test_signature_verify_strict() {
+        let n = Secp256k1Point::order();
+        let p = Secp256k1Felt::prime();
+        let private_key = PrivateKey::new(Secp256k1Felt::new(BigUint::from(12345u32)));
+        let public_key = private_key.public_key();
+        let z = Secp256k1Felt::new(BigUint::from(67890u32));
+        let signature = private_key.sign(&z);
+        let (r, s) = (signature.r().inner().clone(), signature.s().inner().clone());
+        let new = |r: &BigUint, s: &BigUint| {
+            Signature::new(Secp256k1Felt::new(r.clone()), Secp256k1Felt::new(s.clone()))
+        };
+
+        assert!(signature.verify(&z, public_key));
+        assert!(signature.verify_low_s(&z, public_key));
+        assert_eq!(Signature::try_new(r.clone(), s.clone()).unwrap(), signature);
+
+        // Out of range r and s are rejected, never panic, also through the public key
+        let zero = BigUint::from(0u32);
+        for (bad_r, bad_s) in [
+            (zero.clone(), s.clone()),
+            (r.clone(), zero.clone()),
+            (n.clone(), s.clone()),
+            (r.clone(), n.clone()),
+            (&r + &n, s.clone()),
+            (r.clone(), &s + &n),
+        ] {
+            if bad_r < p && bad_s < p {
+                assert!(!new(&bad_r, &bad_s).verify(&z, public_key));
+                assert!(!public_key.verify(&z, &new(&bad_r, &bad_s)));
+            }
+            assert!(Signature::try_new(bad_r, bad_s).is_err());
+        }
+        assert!(Signature::try_new(p.clone(), s.clone()).is_err());
+        assert!(Signature::try_new(r.clone(), (BigUint::from(1u32) << 256) - 1u32).is_err());
+
+        // High s is valid, but not under the low s rule
+        let high_s = new(&r, &(&n - &s));
+        assert!(high_s.verify(&z, public_key));
+        assert!(!high_s.is_low_s());
+        assert!(!high_s.verify_low_s(&z, public_key));
+
+        // z is reduced modulo n
+        assert!(signature.verify(&Secp256k1Felt::new(z.inner() + &n), public_key));
+
+        // Public key at infinity
+        assert!(!signature.verify(&z, &(Secp256k1Point::g() * &n)));
+    }
+
+    #[test]
+    #[allow(clippy::many_single_char_names)]
+    fn test_signature_verify_x_above_order() {
+        // Find R with x in [n, p), whose r is x - n
+        let n = Secp256k1Point::order();
+        let p = Secp256k1Felt::prime();
+        let exponent = (&p + 1u32) / 4u32;
+        let (x, y) = (1u32..1000)
+            .map(|i| &n + i)
+            .find_map(|x| {
+                let y_squared = (x.pow(3) + 7u32) % &p;
+                let y = y_squared.modpow(&exponent, &p);
+                (y.modpow(&BigUint::from(2u32), &p) == y_squared).then_some((x, y))
+            })
+            .unwrap();
+        let point_r = Secp256k1Point::new(x.clone(), y);
+        let r = &x - &n;
+
+        // Pick z and s, then the public key Q = (R - u * G) / v makes the signature valid
+        let z = BigUint::from(1234u32);
+        let s = BigUint::from(5678u32);
+        let s_inverse = s.modpow(&(&n - 2u32), &n);
+        let u = &z * &s_inverse % &n;
+        let v = &r * &s_inverse % &n;
+        let v_inverse = v.modpow(&(&n - 2u32), &n);
+        let public_key = (point_r - Secp256k1Point::g() * u) * v_inverse;
+
+        let signature = Signature::try_new(r, s).unwrap();
+        assert!(signature.verify(&Secp256k1Felt::new(z), &public_key));
+    }
+
+    #[test]
+    fn test_wycheproof_secp256k1() {
+        // Wycheproof ecdsa_secp256k1_sha256_test.json, DER signatures of messages
+        // hashed with SHA-256. Invalid ones must fail to parse or to verify
+        let data: serde_json::Value = serde_json::from_str(include_str!(
+            "../../test_data/wycheproof/ecdsa_secp256k1_sha256_test.json"
+        ))
+        .unwrap();
+
+        for group in data["testGroups"].as_array().unwrap() {
+            let sec = group["publicKey"]["uncompressed"].as_str().unwrap();
+            let public_key = Secp256k1Point::sec_parse(&sec.to_bytes_be());
+
+            for test in group["tests"].as_array().unwrap() {
+                let message = test["msg"].as_str().unwrap().to_bytes_be();
+                let z = Secp256k1Felt::from_bytes(&Sha256::digest(&message));
+                let der = test["sig"].as_str().unwrap().to_bytes_be();
+
+                let valid = Signature::parse_der(&der)
+                    .is_ok_and(|signature| public_key.verify(&z, &signature));
+                assert_eq!(valid, test["result"] == "valid", "tcId {}", test["tcId"]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signature_der() {
+        // Signature of the transaction of chapter 5 of Programming Bitcoin, r needs a
+        // padding byte, s doesn't
+        let der = "3045022100ed81ff192e75a3fd2304004dcadb746fa5e24c5031ccfcf21320b0277457c98f02207a986d955c6e0cb35d446a89d3f56100f4d7f67801c31967743a9c8e10615bed".to_bytes_be();
+        let signature = Signature::parse_der(&der).unwrap();
+        let r = "ed81ff192e75a3fd2304004dcadb746fa5e24c5031ccfcf21320b0277457c98f".to_bytes_be();
+        assert_eq!(signature.r().inner(), &BigUint::from_bytes_be(&r));
+        assert_eq!(signature.der(), der);
+
+        let small = Signature::try_new(BigUint::from(1u32), BigUint::from(0x80u32)).unwrap();
+        assert_eq!(small.der(), vec![0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80]);
+        assert_eq!(Signature::parse_der(&small.der()).unwrap(), small);
+
+        let invalid: [&[u8]; 7] = [
+            // Wrong sequence length
+            &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01],
+            // Negative r
+            &[0x30, 0x06, 0x02, 0x01, 0x81, 0x02, 0x01, 0x01],
+            // Needless padding of r
+            &[0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x01],
+            // Zero length s
+            &[0x30, 0x06, 0x02, 0x02, 0x01, 0x01, 0x02, 0x00],
+            // Trailing byte
+            &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x00],
+            // r of zero
+            &[0x30, 0x06, 0x02, 0x01, 0x00, 0x02, 0x01, 0x01],
+            // Not an integer
+            &[0x30, 0x06, 0x03, 0x01, 0x01, 0x02, 0x01, 0x01],
+        ];
+        for bytes in invalid {
+            assert!(Signature::parse_der(bytes).is_err(), "{bytes:?}");
+        }
+
+        // Only r of zero has a strict DER encoding, it is just out of range
+        assert!(Signature::is_strict_der(&der));
+        for (index, bytes) in invalid.iter().enumerate() {
+            assert_eq!(Signature::is_strict_der(bytes), index == 5, "{bytes:?}");
+        }
+    }
+
+    #[test]
+    fn test_signature_der_lax() {
+        let one = Signature::try_new(BigUint::from(1u32), BigUint::from(1u32)).unwrap();
+        let der = "3045022100ed81ff192e75a3fd2304004dcadb746fa5e24c5031ccfcf21320b0277457c98f02207a986d955c6e0cb35d446a89d3f56100f4d7f67801c31967743a9c8e10615bed".to_bytes_be();
+        assert_eq!(
+            Signature::parse_der_lax(&der).unwrap(),
+            Signature::parse_der(&der).unwrap()
+        );
+
+        // Encodings Bitcoin Core's ecdsa_signature_parse_der_lax accepts, of r = s = 1
+        // except for the negative r
+        let valid: [&[u8]; 7] = [
+            // Wrong sequence length
+            &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01],
+            // Needless padding of r
+            &[0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x01],
+            // Trailing byte
+            &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x00],
+            // Long form lengths, with leading zeros
+            &[0x30, 0x81, 0x06, 0x02, 0x81, 0x01, 0x01, 0x02, 0x82, 0x00, 0x01, 0x01],
+            // r padded beyond 32 bytes
+            &[[0x30, 0x26, 0x02, 0x21].as_slice(), &[0; 32], &[0x01, 0x02, 0x01, 0x01]].concat(),
+            // Sequence length ignored
+            &[0x30, 0x00, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01],
+            // Negative r
+            &[0x30, 0x06, 0x02, 0x01, 0x81, 0x02, 0x01, 0x01],
+        ];
+        for bytes in &valid[..6] {
+            assert_eq!(Signature::parse_der_lax(bytes).unwrap(), one, "{bytes:?}");
+        }
+        assert_eq!(
+            Signature::parse_der_lax(valid[6]).unwrap().r().inner(),
+            &BigUint::from(0x81u32)
+        );
+
+        let invalid: [&[u8]; 7] = [
+            // Zero length s
+            &[0x30, 0x06, 0x02, 0x02, 0x01, 0x01, 0x02, 0x00],
+            // r of zero
+            &[0x30, 0x06, 0x02, 0x01, 0x00, 0x02, 0x01, 0x01],
+            // Not an integer
+            &[0x30, 0x06, 0x03, 0x01, 0x01, 0x02, 0x01, 0x01],
+            // Truncated s
+            &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x02, 0x01],
+            // Length of 4 bytes
+            &[0x30, 0x06, 0x02, 0x84, 0x01, 0x00, 0x00, 0x00],
+            // r not less than n
+            &[[0x30, 0x25, 0x02, 0x20].as_slice(), &[0xff; 32], &[0x02, 0x01, 0x01]].concat(),
+            // Not a sequence
+            &[0x31, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01],
+        ];
+        for bytes in invalid {
+            assert!(Signature::parse_der_lax(bytes).is_err(), "{bytes:?}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by zero")]
+    fn test_secp256k1_felt_div_by_zero() {
+        let _ = Secp256k1Felt::new(BigUint::from(1u32)) / Secp256k1Felt::new(BigUint::from(0u32));
     }
 }
@@ -0,0 +1,152 @@
+use super::{jacobian::JacobianPoint, point::Point};
+use num_bigint::BigUint;
+
+/// Below this many terms `Point::multi_scalar_mul` uses Straus, above it Pippenger
+pub const PIPPENGER_THRESHOLD: usize = 32;
+
+/// Window width of Straus' method
+const STRAUS_WINDOW: u64 = 4;
+
+impl Point {
+    /// Computes the sum of `k_i * P_i`
+    ///
+    /// Uses Straus' method for a few terms and Pippenger's bucket method for many.
+    /// Both share the doublings between all terms, which is what makes them faster
+    /// than summing scalar multiplications one by one
+    ///
+    /// Returns `None` if there are no terms, since there is no curve to take the
+    /// identity of
+    pub fn multi_scalar_mul(terms: &[(BigUint, Point)]) -> Option<Point> {
+        if terms.len() < PIPPENGER_THRESHOLD {
+            Self::straus(terms)
+        } else {
+            Self::pippenger(terms)
+        }
+    }
+
+    /// Sums the scalar multiplications one by one, for comparison
+    pub fn multi_scalar_mul_naive(terms: &[(BigUint, Point)]) -> Option<Point> {
+        let curve = terms.first()?.1.curve.clone();
+        let sum = terms
+            .iter()
+            .fold(JacobianPoint::identity(curve), |sum, (scalar, point)| {
+                sum + JacobianPoint::from(point.clone()).mul(scalar)
+            });
+
+        Some(sum.to_affine())
+    }
+
+    /// Straus' (Shamir's trick) interleaved window method
+    ///
+    /// Precomputes 1P..(2^w - 1)P for every point, then goes through the scalars w
+    /// bits at a time, doubling the accumulator w times and adding the table entry of
+    /// each scalar's window
+    pub fn straus(terms: &[(BigUint, Point)]) -> Option<Point> {
+        let curve = terms.first()?.1.curve.clone();
+        let table_size = 1usize << STRAUS_WINDOW;
+
+        let tables: Vec<Vec<JacobianPoint>> = terms
+            .iter()
+            .map(|(_, point)| {
+                let point = JacobianPoint::from(point.clone());
+                let mut table = vec![JacobianPoint::identity(curve.clone())];
+                for i in 1..table_size {
+                    let next = &table[i - 1] + &point;
+                    table.push(next);
+                }
+                table
+            })
+            .collect();
+
+        let bits = terms
+            .iter()
+            .map(|(scalar, _)| scalar.bits())
+            .max()
+            .unwrap_or_default();
+        let windows = bits.div_ceil(STRAUS_WINDOW);
+
+        let mut sum = JacobianPoint::identity(curve);
+        for window_index in (0..windows).rev() {
+            for _ in 0..STRAUS_WINDOW {
+                sum = sum.double();
+            }
+
+            for ((scalar, _), table) in terms.iter().zip(&tables) {
+                let digit = window(scalar, window_index * STRAUS_WINDOW, STRAUS_WINDOW);
+                if digit != 0 {
+                    sum = sum + &table[digit];
+                }
+            }
+        }
+
+        Some(sum.to_affine())
+    }
+
+    /// Pippenger's bucket method
+    ///
+    /// Splits the scalars in windows of c bits. In each window, points are thrown into
+    /// the bucket of their digit and the window sum, sum of `d * B_d`, is computed with a
+    /// running sum from the highest bucket down. Windows are combined by doubling c
+    /// times. Costs about (b / c) * (n + 2^c) additions for n terms of b bits
+    pub fn pippenger(terms: &[(BigUint, Point)]) -> Option<Point> {
+        let curve = terms.first()?.1.curve.clone();
+        let identity = JacobianPoint::identity(curve);
+
+        let width = pippenger_window(terms.len());
+        let bits = terms
+            .iter()
+            .map(|(scalar, _)| scalar.bits())
+            .max()
+            .unwrap_or_default();
+        let windows = bits.div_ceil(width);
+        let points: Vec<JacobianPoint> = terms
+            .iter()
+            .map(|(_, point)| JacobianPoint::from(point.clone()))
+            .collect();
+
+        let mut sum = identity.clone();
+        for window_index in (0..windows).rev() {
+            for _ in 0..width {
+                sum = sum.double();
+            }
+
+            let mut buckets = vec![identity.clone(); (1 << width) - 1];
+            for ((scalar, _), point) in terms.iter().zip(&points) {
+                let digit = window(scalar, window_index * width, width);
+                if digit != 0 {
+                    buckets[digit - 1] = &buckets[digit - 1] + point;
+                }
+            }
+
+            // sum of `d * B_d` = B_top + (B_top + B_top-1) + ... accumulated from the top
+            let mut running = identity.clone();
+            let mut window_sum = identity.clone();
+            for bucket in buckets.into_iter().rev() {
+                running = running + bucket;
+                window_sum = window_sum + &running;
+            }
+
+            sum = sum + window_sum;
+        }
+
+        Some(sum.to_affine())
+    }
+}
+
+/// Picks the window width of Pippenger's method, about ln(n)
+fn pippenger_window(terms: usize) -> u64 {
+    match terms {
+        0..=31 => 3,
+        32..=127 => 4,
+        128..=511 => 5,
+        512..=2047 => 6,
+        _ => 8,
+    }
+}
+
+/// Returns `width` bits of the scalar starting from bit `start`
+fn window(scalar: &BigUint, start: u64, width: u64) -> usize {
+    (0..width)
+        .filter(|i| scalar.bit(start + i))
+        .fold(0, |digit, i| digit | (1 << i))
+}
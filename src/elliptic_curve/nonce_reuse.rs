@@ -0,0 +1,190 @@
+use super::{
+    private_key::PrivateKey,
+    secp256k1::{Secp256k1Felt, Secp256k1Point},
+    signature::Signature,
+};
+use crate::finite_fields::modulo::Modulo;
+use num_bigint::BigUint;
+use std::collections::HashMap;
+
+/// A signed message hash `z` with its signature and the key that supposedly signed it
+pub type SignedHash = (Secp256k1Felt, Signature, Secp256k1Point);
+
+/// Signatures that share the same r, so the same nonce k
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepeatedNonce {
+    pub r: BigUint,
+    /// Indices of the signatures in the analyzed input
+    pub indices: Vec<usize>,
+}
+
+/// A private key recovered from signatures that reused a nonce
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoveredKey {
+    pub public_key: Secp256k1Point,
+    pub secret: BigUint,
+    /// The reused nonce, up to its sign since low-S signatures hide it
+    pub k: BigUint,
+    pub r: BigUint,
+    /// Indices of the signatures the key was recovered from
+    pub indices: Vec<usize>,
+}
+
+impl RecoveredKey {
+    /// Returns the recovered private key
+    pub fn private_key(&self) -> PrivateKey {
+        PrivateKey::new(Secp256k1Felt::new(self.secret.clone()))
+    }
+}
+
+/// Result of `analyze`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NonceReuseReport {
+    pub repeated: Vec<RepeatedNonce>,
+    pub recovered: Vec<RecoveredKey>,
+}
+
+/// Looks for signatures that reused a nonce and recovers the private keys behind them
+///
+/// With the same k, two signatures of one key give
+///   s1 * k = z1 + r * secret
+///   s2 * k = z2 + r * secret
+/// so k = (z1 - z2) / (s1 - s2) and secret = (s1 * k - z1) / r. Signers that
+/// normalize to low-S may have negated either s, so (s1 + s2) is tried as well.
+///
+/// Once k is known, every other signature with the same r leaks its key too, even if
+/// it was made by a different key. Every recovered key is checked against the public
+/// key and r before it is reported
+pub fn analyze(signatures: &[SignedHash]) -> NonceReuseReport {
+    let mut groups: HashMap<BigUint, Vec<usize>> = HashMap::new();
+    for (index, (_, signature, _)) in signatures.iter().enumerate() {
+        groups
+            .entry(signature.r().inner().clone())
+            .or_default()
+            .push(index);
+    }
+
+    let mut repeated: Vec<RepeatedNonce> = groups
+        .into_iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .map(|(r, indices)| RepeatedNonce { r, indices })
+        .collect();
+    repeated.sort_by_key(|group| group.indices[0]);
+
+    let mut recovered: Vec<RecoveredKey> = Vec::new();
+    for group in &repeated {
+        let Some(k) = find_nonce(signatures, group) else {
+            continue;
+        };
+
+        for &index in &group.indices {
+            let (z, signature, public_key) = &signatures[index];
+            if let Some(key) = recovered
+                .iter_mut()
+                .find(|key| &key.public_key == public_key)
+            {
+                if key.r == group.r && !key.indices.contains(&index) {
+                    key.indices.push(index);
+                }
+                continue;
+            }
+
+            if let Some((k, secret)) = secret_from_nonce(z, signature, public_key, &k) {
+                recovered.push(RecoveredKey {
+                    public_key: public_key.clone(),
+                    secret,
+                    k,
+                    r: group.r.clone(),
+                    indices: vec![index],
+                });
+            }
+        }
+    }
+
+    NonceReuseReport {
+        repeated,
+        recovered,
+    }
+}
+
+/// Recovers the nonce k from a pair of signatures of the same key in the group
+#[allow(clippy::many_single_char_names)]
+fn find_nonce(signatures: &[SignedHash], group: &RepeatedNonce) -> Option<BigUint> {
+    let n = Secp256k1Point::order();
+
+    for (position, &first) in group.indices.iter().enumerate() {
+        for &second in &group.indices[position + 1..] {
+            let (z1, first_signature, first_key) = &signatures[first];
+            let (z2, second_signature, second_key) = &signatures[second];
+            if first_key != second_key {
+                continue;
+            }
+
+            let z_diff = sub_mod(&z1.inner().modulo(&n), &z2.inner().modulo(&n), &n);
+            let (s1, s2) = (first_signature.s().inner(), second_signature.s().inner());
+
+            for s_diff in [sub_mod(s1, s2, &n), (s1 + s2).modulo(&n)] {
+                let Some(s_diff_inverse) = inverse(&s_diff, &n) else {
+                    continue;
+                };
+
+                let k = (&z_diff * s_diff_inverse).modulo(&n);
+                if secret_from_nonce(z1, first_signature, first_key, &k).is_some() {
+                    return Some(k);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Recovers the secret of a signature made with a known nonce, trying both signs of k
+///
+/// Returns the nonce with the sign that was used and the secret, if it matches the
+/// public key
+#[allow(clippy::many_single_char_names)]
+fn secret_from_nonce(
+    z: &Secp256k1Felt,
+    signature: &Signature,
+    public_key: &Secp256k1Point,
+    k: &BigUint,
+) -> Option<(BigUint, BigUint)> {
+    let n = Secp256k1Point::order();
+    let r = signature.r().inner();
+    let r_inverse = inverse(r, &n)?;
+    let z = z.inner().modulo(&n);
+
+    [k.clone(), sub_mod(&n, k, &n)]
+        .into_iter()
+        .filter(|k| k != &BigUint::from(0u32))
+        .find_map(|k| {
+            // secret = (s * k - z) / r
+            let secret = (sub_mod(&(signature.s().inner() * &k), &z, &n) * &r_inverse).modulo(&n);
+            is_valid(&k, &secret, r, public_key).then_some((k, secret))
+        })
+}
+
+/// Checks that k generates r and the secret generates the public key
+fn is_valid(k: &BigUint, secret: &BigUint, r: &BigUint, public_key: &Secp256k1Point) -> bool {
+    let n = Secp256k1Point::order();
+    let Some(x) = (Secp256k1Point::g() * k).x().felt().cloned() else {
+        return false;
+    };
+
+    &x.inner().modulo(&n) == r && &(Secp256k1Point::g() * secret) == public_key
+}
+
+fn sub_mod(a: &BigUint, b: &BigUint, n: &BigUint) -> BigUint {
+    (a.modulo(n) + n - b.modulo(n)).modulo(n)
+}
+
+/// Inverts a scalar modulo the prime order, `None` for zero
+fn inverse(value: &BigUint, n: &BigUint) -> Option<BigUint> {
+    let value = value.modulo(n);
+    if value == BigUint::from(0u32) {
+        return None;
+    }
+
+    Some(value.modpow(&(n - BigUint::from(2u32)), n))
+}
@@ -0,0 +1,239 @@
+use super::{
+    curve::Curve,
+    ecdsa::{EcdsaSignature, SigningKey},
+    jacobian::JacobianPoint,
+    params::CurveParams,
+    point::{Point, PointType},
+};
+use crate::{
+    finite_fields::{element::Felt, macros::impl_refs, modulo::Modulo},
+    helpers::bytes::to_32_bytes,
+};
+use color_eyre::eyre::{eyre, Result};
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Display,
+    ops::{Add, Mul},
+};
+
+/// Signatures on P-256 are plain ECDSA signatures
+pub type P256Signature = EcdsaSignature;
+
+/// Represents a point on NIST P-256 (secp256r1) curve
+#[derive(Debug, Clone, PartialEq)]
+pub struct P256Point(Point);
+
+impl P256Point {
+    pub fn params() -> &'static CurveParams {
+        CurveParams::secp256r1()
+    }
+
+    pub fn order() -> BigUint {
+        Self::params().order.clone()
+    }
+
+    pub fn curve() -> Curve {
+        Self::params().curve.clone()
+    }
+
+    pub fn g() -> Self {
+        Self(Self::params().generator.clone())
+    }
+
+    pub fn x(&self) -> &PointType {
+        &self.0.x
+    }
+
+    pub fn y(&self) -> &PointType {
+        &self.0.y
+    }
+
+    /// Creates a new point on P-256 curve
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the coordinates are not field elements or x and y
+    /// combination is not on the curve
+    pub fn new(x: BigUint, y: BigUint) -> Result<Self> {
+        let prime = Self::params().prime();
+        if &x >= prime || &y >= prime {
+            return Err(eyre!("Coordinates must be less than the prime"));
+        }
+
+        let point =
+            Self::curve().point(Felt::new(x, prime.clone()), Felt::new(y, prime.clone()))?;
+        Ok(Self(point))
+    }
+
+    /// Verifies the signature given the hash of the message `z`
+    /// Returns if the signature is valid
+    pub fn verify(&self, z: &BigUint, signature: &P256Signature) -> bool {
+        signature.verify(Self::params(), z, &self.0)
+    }
+
+    /// Verifies the signature of a message hashed with SHA-256
+    pub fn verify_slice(&self, message: &[u8], signature: &P256Signature) -> bool {
+        self.verify(&message_hash(message), signature)
+    }
+
+    /// Serializes the point in uncompressed SEC format
+    ///
+    /// Returns a 65 byte array where
+    /// - the first byte is 0x04
+    /// - the next 32 bytes are the x coordinate
+    /// - the last 32 bytes are the y coordinate
+    ///
+    /// Returns None if the point is at infinity
+    pub fn sec_uncompressed(&self) -> Option<Vec<u8>> {
+        let (PointType::Normal(x), PointType::Normal(y)) = (self.x(), self.y()) else {
+            return None;
+        };
+
+        let mut result = vec![0x04];
+        result.extend_from_slice(&to_32_bytes(x.inner()));
+        result.extend_from_slice(&to_32_bytes(y.inner()));
+        Some(result)
+    }
+
+    /// Serializes the point in compressed SEC format
+    ///
+    /// Returns a 33 byte array where the first byte is 0x02 if y is even, 0x03 if y is
+    /// odd and the next 32 bytes are the x coordinate
+    ///
+    /// Returns None if the point is at infinity
+    pub fn sec_compressed(&self) -> Option<Vec<u8>> {
+        let (PointType::Normal(x), PointType::Normal(y)) = (self.x(), self.y()) else {
+            return None;
+        };
+
+        let mut result = vec![if y.inner().bit(0) { 0x03 } else { 0x02 }];
+        result.extend_from_slice(&to_32_bytes(x.inner()));
+        Some(result)
+    }
+
+    /// Parses a point from SEC format
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid SEC encoding of a point on the curve
+    pub fn sec_parse(sec_bytes: &[u8]) -> Result<Self> {
+        match (sec_bytes.first(), sec_bytes.len()) {
+            (Some(0x04), 65) => {
+                let x = BigUint::from_bytes_be(&sec_bytes[1..33]);
+                let y = BigUint::from_bytes_be(&sec_bytes[33..65]);
+                Self::new(x, y)
+            }
+            (Some(prefix @ (0x02 | 0x03)), 33) => {
+                let x = BigUint::from_bytes_be(&sec_bytes[1..]);
+                let prime = Self::params().prime();
+                if &x >= prime {
+                    return Err(eyre!("x must be less than the prime"));
+                }
+
+                let point =
+                    Self::curve().point_from_x(Felt::new(x, prime.clone()), *prefix == 0x02)?;
+                Ok(Self(point))
+            }
+            _ => Err(eyre!("Invalid SEC encoding")),
+        }
+    }
+}
+
+impl Display for P256Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let x_repr = match self.x() {
+            PointType::Normal(x) => x.inner().to_str_radix(16),
+            PointType::Infinity => "Infinity".to_string(),
+        };
+
+        let y_repr = match self.y() {
+            PointType::Normal(y) => y.inner().to_str_radix(16),
+            PointType::Infinity => "Infinity".to_string(),
+        };
+
+        write!(f, "Point {{ x: 0x{x_repr:0>64}, y: 0x{y_repr:0>64} }}")
+    }
+}
+
+impl From<P256Point> for Point {
+    fn from(point: P256Point) -> Self {
+        point.0
+    }
+}
+
+impl Add<P256Point> for P256Point {
+    type Output = Self;
+
+    fn add(self, rhs: P256Point) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl_refs!(Add, add, P256Point, P256Point);
+
+impl Mul<BigUint> for P256Point {
+    type Output = Self;
+
+    /// Multiplies a P-256 point by a scalar
+    ///
+    /// Since we know the order of the group, we can take the modulo of the scalar
+    fn mul(self, coefficient: BigUint) -> Self::Output {
+        let coefficient = coefficient.modulo(&Self::order());
+        Self(JacobianPoint::from(self.0).mul(&coefficient).to_affine())
+    }
+}
+
+impl_refs!(Mul, mul, P256Point, BigUint);
+
+/// Represents a private key on P-256 curve
+#[derive(Debug, Clone)]
+pub struct P256PrivateKey {
+    key: SigningKey,
+    public_key: P256Point,
+}
+
+impl P256PrivateKey {
+    /// Creates a new private key from a secret scalar
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret is not in [1, n-1]
+    pub fn new(secret: BigUint) -> Result<Self> {
+        let key = SigningKey::new(P256Point::params(), secret)?;
+        let public_key = P256Point(key.public_key().clone());
+        Ok(Self { key, public_key })
+    }
+
+    /// Returns the public key for this private key
+    pub fn public_key(&self) -> &P256Point {
+        &self.public_key
+    }
+
+    /// Signs the hash of a message with a deterministic k (RFC 6979)
+    pub fn sign(&self, z: &BigUint) -> P256Signature {
+        self.key.sign(z)
+    }
+
+    /// Signs a byte slice using the private key
+    /// This is a convenience method that hashes the message with SHA-256 before signing
+    pub fn sign_slice(&self, message: &[u8]) -> P256Signature {
+        self.sign(&message_hash(message))
+    }
+
+    /// Checks if message is signed by this private key
+    pub fn verify(&self, z: &BigUint, signature: &P256Signature) -> bool {
+        self.public_key.verify(z, signature)
+    }
+
+    /// Convenience method to verify a slice after hashing it
+    pub fn verify_slice(&self, message: &[u8], signature: &P256Signature) -> bool {
+        self.public_key.verify_slice(message, signature)
+    }
+}
+
+/// Hashes the message with SHA-256, the digest is as long as the order so it is
+/// used as z directly
+fn message_hash(message: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(&Sha256::digest(message))
+}
@@ -0,0 +1,138 @@
+use super::{curve::Curve, point::Point};
+use crate::finite_fields::element::Felt;
+use num_bigint::BigUint;
+use std::sync::OnceLock;
+
+/// Represents the domain parameters of a curve used for cryptography
+///
+/// - `curve`: y^2 = x^3 + ax + b over the prime field p
+/// - `generator`: the base point G
+/// - `order`: n, the order of G, n * G is the identity
+/// - `cofactor`: h, the number of points on the curve divided by n
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveParams {
+    pub name: &'static str,
+    pub curve: Curve,
+    pub generator: Point,
+    pub order: BigUint,
+    pub cofactor: BigUint,
+}
+
+impl CurveParams {
+    /// Creates a new parameter set
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator is not on the curve
+    #[allow(clippy::too_many_arguments, clippy::many_single_char_names)]
+    pub fn new(
+        name: &'static str,
+        p: BigUint,
+        a: BigUint,
+        b: BigUint,
+        g: (BigUint, BigUint),
+        n: BigUint,
+        h: BigUint,
+    ) -> Self {
+        let curve = Curve::new(Felt::new(a, p.clone()), Felt::new(b, p.clone()));
+        let generator = curve
+            .point(Felt::new(g.0, p.clone()), Felt::new(g.1, p))
+            .expect("Generator is not on the curve");
+
+        Self {
+            name,
+            curve,
+            generator,
+            order: n,
+            cofactor: h,
+        }
+    }
+
+    /// Creates a new parameter set from hex strings
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value is not valid hex or the generator is not on the curve
+    #[allow(clippy::many_single_char_names)]
+    fn from_hex(
+        name: &'static str,
+        p: &str,
+        a: &str,
+        b: &str,
+        g: (&str, &str),
+        n: &str,
+        h: u32,
+    ) -> Self {
+        let parse = |value: &str| BigUint::parse_bytes(value.as_bytes(), 16).expect("Invalid hex");
+        Self::new(
+            name,
+            parse(p),
+            parse(a),
+            parse(b),
+            (parse(g.0), parse(g.1)),
+            parse(n),
+            BigUint::from(h),
+        )
+    }
+
+    /// Returns the prime of the field the curve is defined over
+    pub fn prime(&self) -> &BigUint {
+        self.curve.a.prime()
+    }
+
+    /// secp256k1, the curve used by Bitcoin
+    /// <https://www.secg.org/sec2-v2.pdf>
+    pub fn secp256k1() -> &'static CurveParams {
+        static PARAMS: OnceLock<CurveParams> = OnceLock::new();
+        PARAMS.get_or_init(|| {
+            Self::from_hex(
+                "secp256k1",
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+                "0",
+                "7",
+                (
+                    "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                    "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+                ),
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                1,
+            )
+        })
+    }
+
+    /// secp256r1, also known as NIST P-256 or prime256v1
+    /// <https://www.secg.org/sec2-v2.pdf>
+    pub fn secp256r1() -> &'static CurveParams {
+        static PARAMS: OnceLock<CurveParams> = OnceLock::new();
+        PARAMS.get_or_init(|| {
+            Self::from_hex(
+                "secp256r1",
+                "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+                "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
+                "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+                (
+                    "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+                    "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+                ),
+                "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+                1,
+            )
+        })
+    }
+
+    /// y^2 = x^3 + 7 over `F_223` from Programming Bitcoin, with G = (47, 71)
+    ///
+    /// The curve has 252 points and G generates a subgroup of 21 of them
+    pub fn toy_223() -> &'static CurveParams {
+        static PARAMS: OnceLock<CurveParams> = OnceLock::new();
+        PARAMS.get_or_init(|| Self::from_hex("toy223", "df", "0", "7", ("2f", "47"), "15", 12))
+    }
+
+    /// y^2 = x^3 + 7 over `F_223` from Programming Bitcoin, with G = (15, 86)
+    ///
+    /// G generates a subgroup of 7 points
+    pub fn toy_223_order_7() -> &'static CurveParams {
+        static PARAMS: OnceLock<CurveParams> = OnceLock::new();
+        PARAMS.get_or_init(|| Self::from_hex("toy223-7", "df", "0", "7", ("f", "56"), "7", 36))
+    }
+}
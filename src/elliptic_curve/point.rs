@@ -1,8 +1,14 @@
 use super::curve::Curve;
-use crate::finite_fields::{element::Felt, macros::impl_refs, pow::Pow};
+use crate::{
+    finite_fields::{element::Felt, macros::impl_refs, pow::Pow},
+    helpers::arithmetic::factorize,
+};
 use color_eyre::eyre::{eyre, Result};
 use num_bigint::BigUint;
-use std::ops::{Add, BitAnd, Mul};
+use std::ops::{Add, BitAnd, Mul, Neg, Sub};
+
+/// Largest subgroup `Point::subgroup` enumerates, every point is kept in memory
+pub const MAX_SUBGROUP_ORDER: usize = 1 << 12;
 
 /// Represents type of a point on an elliptic curve
 ///
@@ -14,17 +20,25 @@ pub enum PointType {
 }
 
 impl PointType {
-    /// Unwraps the point type
-    ///
-    /// # Panics
-    ///
-    /// Panics if the point type is infinity
-    pub fn unwrap(self) -> Felt {
+    /// Returns the coordinate, `None` for infinity
+    pub fn felt(&self) -> Option<&Felt> {
         match self {
-            Self::Infinity => panic!("Cannot unwrap infinity"),
-            Self::Normal(felt) => felt,
+            Self::Infinity => None,
+            Self::Normal(felt) => Some(felt),
         }
     }
+
+    /// Converts into the coordinate, `None` for infinity
+    pub fn into_felt(self) -> Option<Felt> {
+        match self {
+            Self::Infinity => None,
+            Self::Normal(felt) => Some(felt),
+        }
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        matches!(self, Self::Infinity)
+    }
 }
 
 /// Represents a point on an elliptic curve
@@ -36,6 +50,39 @@ pub struct Point {
 }
 
 impl Point {
+    /// Checks if the point is the identity, the point at infinity
+    pub fn is_infinity(&self) -> bool {
+        self.x.is_infinity()
+    }
+
+    /// Returns the (x, y) coordinates, `None` for the point at infinity
+    pub fn coordinates(&self) -> Option<(&Felt, &Felt)> {
+        Some((self.x.felt()?, self.y.felt()?))
+    }
+
+    /// Doubles the point, P + P
+    ///
+    /// Formula (x3,y3)=(x1,y1)+(x1,y1)
+    ///  s = (3 * x1**2 + a) / (2 * y1)
+    /// x3 = s**2 - 2 * x1
+    /// y3 = s * (x1-x3) - y1
+    ///
+    /// If y1 is 0 the tangent is vertical and we return the point at infinity
+    pub fn double(&self) -> Self {
+        let Some((x1, y1)) = self.coordinates() else {
+            return self.clone();
+        };
+
+        if y1.is_zero() {
+            return self.curve.identity();
+        }
+
+        let s = (&x1.pow(2u32) * 3u32 + &self.curve.a) / (y1 * 2u32);
+        let x3 = &s.pow(2u32) - x1 * 2u32;
+        let y3 = &s * (x1 - &x3) - y1;
+        Self::new(PointType::Normal(x3), PointType::Normal(y3), self.curve.clone())
+    }
+
     /// Checks if the point is on the curve
     pub fn is_on_curve(&self) -> bool {
         match (&self.x, &self.y) {
@@ -134,7 +181,7 @@ impl Point {
             if coefficient & 1 == 1 {
                 result = result + &current;
             }
-            current = &current + &current;
+            current = current.double();
             coefficient >>= 1;
         }
 
@@ -154,7 +201,7 @@ impl Point {
             if coefficient.clone().bitand(one.clone()) == one.clone() {
                 result = result + &current;
             }
-            current = &current + &current;
+            current = current.double();
             coefficient >>= 1;
         }
 
@@ -162,6 +209,86 @@ impl Point {
     }
 }
 
+impl Point {
+    /// Returns the order of the point given a multiple of it, e.g. the number of
+    /// points on the curve
+    ///
+    /// Starting from the multiple, divides out prime factors q as long as the result
+    /// still sends the point to infinity
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the multiple doesn't fit in a `u64` or is not a multiple
+    /// of the order
+    pub fn order_in(&self, multiple: &BigUint) -> Result<BigUint> {
+        let identity = self.curve.identity();
+        if self.clone() * multiple.clone() != identity {
+            return Err(eyre!("{} is not a multiple of the order of the point", multiple));
+        }
+
+        let mut order = u64::try_from(multiple)?;
+        for (prime, _) in factorize(order) {
+            while order % prime == 0 && self.clone() * BigUint::from(order / prime) == identity {
+                order /= prime;
+            }
+        }
+
+        Ok(BigUint::from(order))
+    }
+
+    /// Returns the order of the point, the smallest n such that n * P is infinity
+    ///
+    /// # Errors
+    ///
+    /// Propagates if `Curve::count_points` returns an error
+    pub fn order(&self) -> Result<BigUint> {
+        self.order_in(&self.curve.count_points()?)
+    }
+
+    /// Returns the cofactor of the subgroup generated by the point, which is the
+    /// number of points on the curve divided by the order of the point
+    ///
+    /// # Errors
+    ///
+    /// Propagates if `Curve::count_points` returns an error
+    pub fn cofactor(&self) -> Result<BigUint> {
+        let points = self.curve.count_points()?;
+        Ok(&points / self.order_in(&points)?)
+    }
+
+    /// Returns the points of the subgroup generated by the point
+    /// [O, P, 2P, ..., (n-1)P]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the order of the point is above `MAX_SUBGROUP_ORDER`
+    pub fn subgroup(&self) -> Result<Vec<Point>> {
+        let identity = self.curve.identity();
+        let mut points = vec![identity.clone()];
+        let mut current = self.clone();
+
+        while current != identity {
+            if points.len() == MAX_SUBGROUP_ORDER {
+                return Err(eyre!("Subgroup has more than {MAX_SUBGROUP_ORDER} points"));
+            }
+            points.push(current.clone());
+            current = current + self;
+        }
+
+        Ok(points)
+    }
+
+    /// Hashable representation of the point, `None` for the point at infinity
+    pub(crate) fn key(&self) -> Option<(BigUint, BigUint)> {
+        match (&self.x, &self.y) {
+            (PointType::Normal(x), PointType::Normal(y)) => {
+                Some((x.inner().clone(), y.inner().clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
 impl Add for Point {
     type Output = Self;
 
@@ -171,59 +298,71 @@ impl Add for Point {
     /// In a high level, given points P1(x1,y1) and P2(x2,y2) on an elliptic curve, adding P1 and P2
     /// means drawing a line through P1 and P2 and finding the point where the line intersects the
     /// curve and taking its reflection across the x-axis.
-    #[allow(clippy::erasing_op)]
     fn add(self, rhs: Self) -> Self::Output {
-        match &self.x {
-            PointType::Normal(x1) => match &rhs.x {
-                PointType::Normal(x2) => {
-                    let y1 = self.y.unwrap();
-                    let y2 = rhs.y.unwrap();
-
-                    if x1 == x2 && y1 != y2 {
-                        // Case 1: self.x == rhs.x && self.y != rhs.y; return Infinity
-                        // If we are on the same x but different y, we are tangent to the vertical line
-                        Self::new(PointType::Infinity, PointType::Infinity, self.curve)
-                    } else if x1 != x2 {
-                        // Case 2: self.x != rhs.x
-                        // Formula (x3,y3) == (x1,y1) + (x2,y2)
-                        //  s = (y2-y1) / (x2-x1)
-                        // x3 = s ** 2 - x1 - x2
-                        // y3 = s * (x1-x3) - y1
-                        let s = (&y2 - &y1) / (x2 - x1);
-                        let x3 = s.pow(2u32) - x1 - x2;
-                        let y3 = s * (x1 - &x3) - &y1;
-
-                        Self::new(PointType::Normal(x3), PointType::Normal(y3), self.curve)
-                    } else if y1 == x1 * 0u32 {
-                        // Case 4: if we are tangent to the vertical line, we return the point at infinity
-                        // note instead of figuring out what 0 is for each type we just use 0 * self.x
-                        Self::new(PointType::Infinity, PointType::Infinity, self.curve)
-                    } else {
-                        // Case 3: self == other
-                        // Formula (x3,y3)=(x1,y1)+(x1,y1)
-                        //  s = (3 * x1**2 + a) / (2 * y1)
-                        // x3 = s**2 - 2 * x1
-                        // y3 = s * (x1-x3) - y1
-                        let curve = &self.curve;
-                        let s = (&x1.pow(2u32) * 3u32 + &curve.a) / (&y1 * 2u32);
-                        let x3 = &s.pow(2u32) - x1 * 2u32;
-                        let y3 = &s * (x1 - &x3) - &y1;
-                        Self::new(PointType::Normal(x3), PointType::Normal(y3), self.curve)
-                    }
-                }
-                // Case 0.1: rhs points to Infinity, return self
-                // This is the identity element for addition
-                PointType::Infinity => self,
-            },
-            // Case 0.0: self points to Infinity, return rhs
+        let (Some((x1, y1)), Some((x2, y2))) = (self.coordinates(), rhs.coordinates()) else {
+            // Case 0: one of the points is Infinity, return the other one
             // This is the identity element for addition
-            PointType::Infinity => rhs,
+            return if self.is_infinity() { rhs } else { self };
+        };
+
+        if x1 == x2 && y1 != y2 {
+            // Case 1: self.x == rhs.x && self.y != rhs.y; return Infinity
+            // If we are on the same x but different y, we are tangent to the vertical line
+            self.curve.identity()
+        } else if x1 != x2 {
+            // Case 2: self.x != rhs.x
+            // Formula (x3,y3) == (x1,y1) + (x2,y2)
+            //  s = (y2-y1) / (x2-x1)
+            // x3 = s ** 2 - x1 - x2
+            // y3 = s * (x1-x3) - y1
+            let s = (y2 - y1) / (x2 - x1);
+            let x3 = s.pow(2u32) - x1 - x2;
+            let y3 = s * (x1 - &x3) - y1;
+
+            Self::new(PointType::Normal(x3), PointType::Normal(y3), self.curve.clone())
+        } else {
+            // Case 3: self == other
+            self.double()
         }
     }
 }
 
 impl_refs!(Add, add, Point, Point);
 
+impl Neg for Point {
+    type Output = Self;
+
+    /// Reflects the point across the x-axis, -(x, y) = (x, -y)
+    fn neg(self) -> Self::Output {
+        match self.y {
+            PointType::Normal(y) => {
+                let zero = Felt::new(BigUint::from(0u32), y.prime().clone());
+                Self::new(self.x, PointType::Normal(zero - y), self.curve)
+            }
+            PointType::Infinity => self,
+        }
+    }
+}
+
+impl Neg for &Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    /// P - Q = P + (-Q)
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl_refs!(Sub, sub, Point, Point);
+
 impl Mul<u32> for Point {
     type Output = Self;
 
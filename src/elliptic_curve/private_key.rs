@@ -1,13 +1,12 @@
 use crate::helpers::hash::hash256;
 
 use super::{
+    rfc6979::Rfc6979,
     secp256k1::{Secp256k1Felt, Secp256k1Point},
     signature::{self, Signature},
 };
-use hmac::{Hmac, Mac};
 use num_bigint::BigUint;
 use sha2::Sha256;
-use std::io::Read;
 
 #[derive(Debug, Clone)]
 pub struct PrivateKey {
@@ -15,8 +14,6 @@ pub struct PrivateKey {
     public_key: Secp256k1Point,
 }
 
-type Hmac256 = Hmac<Sha256>;
-
 impl PrivateKey {
     /// Creates a new private key from a field element
     pub fn new(secret: Secp256k1Felt) -> Self {
@@ -34,16 +31,60 @@ impl PrivateKey {
     /// # Panics
     ///
     /// This method will panic if the field element is not a valid field element
-    #[allow(clippy::many_single_char_names)]
     pub fn sign(&self, z: &Secp256k1Felt) -> Signature {
-        let k = self.deterministic_k(z.inner().clone());
+        self.sign_with_k(z, &self.deterministic_k(z.inner(), None))
+    }
+
+    /// Signs a field element with the extra entropy mixed into the nonce
+    /// (RFC 6979 section 3.6)
+    ///
+    /// The signature is still deterministic for the same entropy, but differs from
+    /// the one `sign` creates
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the field element is not a valid field element
+    pub fn sign_with_extra_entropy(&self, z: &Secp256k1Felt, extra_entropy: &[u8]) -> Signature {
+        self.sign_with_k(z, &self.deterministic_k(z.inner(), Some(extra_entropy)))
+    }
 
+    /// Signs like Bitcoin Core does, grinding the nonce until r is low (r < 2^255)
+    ///
+    /// The first attempt is the plain RFC 6979 signature of `sign`. Each retry mixes
+    /// in a counter as extra entropy, written little endian in the first 4 of 32
+    /// bytes. A low r saves a byte in the DER encoding, so fees can be estimated
+    /// with a fixed signature size. Takes two attempts on average
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the field element is not a valid field element
+    pub fn sign_low_r(&self, z: &Secp256k1Felt) -> Signature {
+        let mut signature = self.sign(z);
+        let mut counter = 0u32;
+
+        while !signature.has_low_r() {
+            counter += 1;
+            let mut extra_entropy = [0u8; 32];
+            extra_entropy[..4].copy_from_slice(&counter.to_le_bytes());
+            signature = self.sign_with_extra_entropy(z, &extra_entropy);
+        }
+
+        signature
+    }
+
+    /// Signs a field element with the given k, normalizing s to the lower half
+    #[allow(clippy::many_single_char_names)]
+    fn sign_with_k(&self, z: &Secp256k1Felt, k: &BigUint) -> Signature {
         // r = (k * G).x
-        let g_x = (Secp256k1Point::g() * &k).x().clone().unwrap();
+        let g_x = (Secp256k1Point::g() * k)
+            .x()
+            .felt()
+            .cloned()
+            .expect("k is less than the order, so k * G is not infinity");
         let r = Secp256k1Felt::from(g_x);
 
         // s = (z + r * secret) / k
-        let k = Secp256k1Felt::new(k);
+        let k = Secp256k1Felt::new(k.clone());
         let mut s = (z + &r * &self.secret) / &k;
 
         // if s > n / 2 then s = n - s
@@ -82,85 +123,20 @@ impl PrivateKey {
         self.verify(&z, sig)
     }
 
-    /// Creates a unique, deterministic k value
+    /// Creates a unique, deterministic k value, mixing in the extra entropy if given
     ///
     /// This is important because if the same k value is used twice, the private key can
-    /// be recovered using both signatures.
+    /// be recovered using both signatures, see `nonce_reuse::analyze`.
     ///
-    /// The specification for determining k is defined in RFC 6779 (<https://tools.ietf.org/html/rfc6979>)
-    fn deterministic_k(&self, z: BigUint) -> BigUint {
-        let k = [0u8; 32];
-        let v = [1u8; 32];
-
-        let mut z = z;
-        if z > Secp256k1Point::order() {
-            z -= Secp256k1Point::order();
-        }
-
-        let z = z.to_bytes_be();
-        let mut z_bytes = vec![0; 32 - z.len()];
-        z_bytes.extend_from_slice(z.as_slice());
-
-        // Pad secret to 32 bytes, from the left
-        let secret = self.secret.inner().to_bytes_be();
-        let mut secret_bytes = vec![0; 32 - secret.len()];
-        secret_bytes.extend_from_slice(secret.as_slice());
-
-        // k := hmac_k (v || 0x00 || secret_bytes || z_bytes)
-        let mut hmac = Hmac256::new_from_slice(&k).unwrap();
-        let mut data = Vec::new();
-        data.extend_from_slice(&v);
-        data.push(0);
-        data.extend_from_slice(&secret_bytes);
-        data.extend_from_slice(&z_bytes);
-        hmac.update(&data);
-        let k = hmac.finalize().into_bytes();
-
-        // v := hmac_k (v)
-        let mut hmac = Hmac256::new_from_slice(&k).unwrap();
-        hmac.update(&v);
-        let v = hmac.finalize().into_bytes();
-
-        // k := hmac_k (v || 0x01 || secret_bytes || z_bytes)
-        let mut hmac = Hmac256::new_from_slice(&k).unwrap();
-        let mut data = Vec::new();
-        data.extend_from_slice(&v);
-        data.push(1);
-        data.extend_from_slice(&secret_bytes);
-        data.extend_from_slice(&z_bytes);
-        hmac.update(&data);
-        let mut k = hmac.finalize().into_bytes();
-
-        // v := hmac_k (v)
-        let mut hmac = Hmac256::new_from_slice(&k).unwrap();
-        hmac.update(&v);
-        let mut v = hmac.finalize().into_bytes();
-
-        loop {
-            // v := hmac_k (v)
-            let mut hmac = Hmac256::new_from_slice(&k).unwrap();
-            hmac.update(&v);
-            v = hmac.finalize().into_bytes();
-
-            let candidate = BigUint::from_bytes_be(&v);
-            let one = BigUint::from(1u8);
-
-            if candidate > one && candidate < Secp256k1Point::order() {
-                return candidate;
-            }
-
-            // k := hmac_k (v || 0x00)
-            let mut hmac = Hmac256::new_from_slice(&k).unwrap();
-            let mut data = Vec::new();
-            data.extend_from_slice(&v);
-            data.push(0);
-            hmac.update(&data);
-            k = hmac.finalize().into_bytes();
-
-            // v := hmac_k (v)
-            let mut hmac = Hmac256::new_from_slice(&k).unwrap();
-            hmac.update(&v);
-            v = hmac.finalize().into_bytes();
-        }
+    /// The specification for determining k is defined in RFC 6979 (<https://tools.ietf.org/html/rfc6979>)
+    fn deterministic_k(&self, z: &BigUint, extra_entropy: Option<&[u8]>) -> BigUint {
+        Rfc6979::<Sha256>::from_z(
+            &Secp256k1Point::order(),
+            self.secret.inner(),
+            z,
+            extra_entropy,
+        )
+        .next()
+        .expect("Nonce generator never ends")
     }
 }
@@ -0,0 +1,148 @@
+use crate::finite_fields::modulo::Modulo;
+use hmac::{
+    digest::{core_api::BlockSizeUser, Digest},
+    Mac, SimpleHmac,
+};
+use num_bigint::BigUint;
+use sha2::Sha256;
+use std::marker::PhantomData;
+
+/// Generates the sequence of deterministic nonces of RFC 6979 with HMAC-DRBG over the
+/// hash function `D`
+/// <https://tools.ietf.org/html/rfc6979#section-3.2>
+///
+/// The first value is the nonce to use, the next ones are used if it turns out to be
+/// unsuitable, e.g. r = 0 on tiny curves.
+///
+/// Additional data k' of section 3.6 can be mixed in. libsecp256k1 uses it for fresh
+/// randomness against fault attacks, for anti-exfil and Bitcoin Core grinds low R
+/// signatures with a counter in it. Without it the nonces are the ones of the
+/// RFC test vectors
+pub struct Rfc6979<D: Digest + BlockSizeUser = Sha256> {
+    order: BigUint,
+    k: Vec<u8>,
+    v: Vec<u8>,
+    first: bool,
+    hash: PhantomData<D>,
+}
+
+impl<D: Digest + BlockSizeUser> Rfc6979<D> {
+    /// Creates the generator from the message hash h1, which can be of any length
+    ///
+    /// h1 is turned into an integer with bits2int and reduced modulo the order, as
+    /// bits2octets of the RFC does
+    pub fn new(
+        order: &BigUint,
+        secret: &BigUint,
+        hash: &[u8],
+        extra_entropy: Option<&[u8]>,
+    ) -> Self {
+        let z = bits2int(hash, order.bits());
+        Self::from_z(order, secret, &z, extra_entropy)
+    }
+
+    /// Creates the generator from the message hash as an integer z, as used in signing
+    ///
+    /// z is reduced modulo the order, so z >= n is handled the same as z - n
+    pub fn from_z(
+        order: &BigUint,
+        secret: &BigUint,
+        z: &BigUint,
+        extra_entropy: Option<&[u8]>,
+    ) -> Self {
+        let rlen = rlen(order);
+        let secret_bytes = int2octets(secret, rlen);
+        let z_bytes = int2octets(&z.modulo(order), rlen);
+        let extra_entropy = extra_entropy.unwrap_or_default();
+
+        let hlen = <D as Digest>::output_size();
+        let mut nonces = Self {
+            order: order.clone(),
+            k: vec![0u8; hlen],
+            v: vec![1u8; hlen],
+            first: true,
+            hash: PhantomData,
+        };
+
+        // k := hmac_k (v || 0x00 || secret_bytes || z_bytes || k')
+        // v := hmac_k (v)
+        // k := hmac_k (v || 0x01 || secret_bytes || z_bytes || k')
+        // v := hmac_k (v)
+        for separator in [0u8, 1] {
+            nonces.k = nonces.hmac(&[
+                &nonces.v,
+                &[separator],
+                &secret_bytes,
+                &z_bytes,
+                extra_entropy,
+            ]);
+            nonces.v = nonces.hmac(&[&nonces.v]);
+        }
+
+        nonces
+    }
+
+    fn hmac(&self, data: &[&[u8]]) -> Vec<u8> {
+        let mut hmac =
+            <SimpleHmac<D> as Mac>::new_from_slice(&self.k).expect("HMAC accepts any key size");
+        for chunk in data {
+            hmac.update(chunk);
+        }
+        hmac.finalize().into_bytes().to_vec()
+    }
+}
+
+impl<D: Digest + BlockSizeUser> Iterator for Rfc6979<D> {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rlen = rlen(&self.order);
+
+        loop {
+            if !self.first {
+                // k := hmac_k (v || 0x00)
+                // v := hmac_k (v)
+                self.k = self.hmac(&[&self.v, &[0]]);
+                self.v = self.hmac(&[&self.v]);
+            }
+            self.first = false;
+
+            let mut t = Vec::with_capacity(rlen);
+            while t.len() < rlen {
+                self.v = self.hmac(&[&self.v]);
+                t.extend_from_slice(&self.v);
+            }
+
+            let candidate = bits2int(&t, self.order.bits());
+            if candidate >= BigUint::from(1u32) && candidate < self.order {
+                return Some(candidate);
+            }
+        }
+    }
+}
+
+/// Takes the leftmost `qlen` bits of the bytes as an integer
+/// <https://tools.ietf.org/html/rfc6979#section-2.3.2>
+pub fn bits2int(bytes: &[u8], qlen: u64) -> BigUint {
+    let value = BigUint::from_bytes_be(bytes);
+    let bits = bytes.len() as u64 * 8;
+
+    if bits > qlen {
+        value >> (bits - qlen)
+    } else {
+        value
+    }
+}
+
+/// Length of the order in bytes
+fn rlen(order: &BigUint) -> usize {
+    usize::try_from(order.bits().div_ceil(8)).expect("Order is too large")
+}
+
+/// Pads the integer to `rlen` bytes, from the left
+fn int2octets(value: &BigUint, rlen: usize) -> Vec<u8> {
+    let bytes = value.to_bytes_be();
+    let mut padded = vec![0u8; rlen.saturating_sub(bytes.len())];
+    padded.extend_from_slice(&bytes);
+    padded
+}
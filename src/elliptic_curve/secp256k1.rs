@@ -1,16 +1,42 @@
 use super::{
     curve::Curve,
+    hash_to_curve,
+    params::CurveParams,
     point::{Point, PointType},
     signature::Signature,
 };
-use crate::finite_fields::{element::Felt, macros::impl_refs, modulo::Modulo};
+use crate::{
+    finite_fields::{
+        batch,
+        element::Felt,
+        field::{Field, Fp},
+        macros::impl_refs,
+        modulo::Modulo,
+    },
+    helpers::bytes::to_32_bytes,
+};
 use color_eyre::eyre::{eyre, Result};
 use num_bigint::BigUint;
 use std::{
     fmt::Display,
-    ops::{Add, Div, Mul},
+    ops::{Add, Div, Mul, Neg, Sub},
+    sync::OnceLock,
 };
 
+/// Prime field of secp256k1 coordinates, 2^256 - 2^32 - 977
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Secp256k1Prime;
+
+impl Field for Secp256k1Prime {
+    fn prime() -> &'static BigUint {
+        static PRIME: OnceLock<BigUint> = OnceLock::new();
+        PRIME.get_or_init(|| BigUint::parse_bytes(Secp256k1Felt::SECP256K1_PRIME, 16).unwrap())
+    }
+}
+
+/// Typed element of the secp256k1 coordinate field
+pub type Secp256k1Fp = Fp<Secp256k1Prime>;
+
 /// Represents a field element on SECP256K1 curve where the prime of the element is
 /// 2^256 - 2^32 - 977
 #[derive(Debug, Clone, PartialEq)]
@@ -23,11 +49,11 @@ impl Secp256k1Felt {
         b"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
 
     pub fn order() -> BigUint {
-        BigUint::parse_bytes(Self::SECP256K1_ORDER, 16).unwrap_or_default()
+        CurveParams::secp256k1().order.clone()
     }
 
     pub fn prime() -> BigUint {
-        BigUint::parse_bytes(Self::SECP256K1_PRIME, 16).unwrap_or_default()
+        Secp256k1Prime::prime().clone()
     }
 
     pub fn new(inner: BigUint) -> Self {
@@ -43,14 +69,30 @@ impl Secp256k1Felt {
         self.0.inner()
     }
 
+    /// Inverts all elements in place, modulo the order like `Div` does
+    ///
+    /// Zero elements are left as zero. See `batch::batch_invert`
+    pub fn batch_invert(elements: &mut [Secp256k1Felt]) {
+        let one = Self::new(BigUint::from(1u32));
+        batch::batch_invert(elements, &one, |element| element.0.is_zero());
+    }
+
     /// Returns the square root of the element
     /// w^2 = v
     /// w = v^(p+1) / 4
-    pub fn sqrt(&self) -> Self {
+    ///
+    /// This shortcut works because p = 3 mod 4. Returns `None` if the element is not
+    /// a square
+    pub fn sqrt(&self) -> Option<Self> {
         let prime = self.0.prime();
         let exponent: BigUint = (prime + BigUint::from(1u32)) / BigUint::from(4u32);
-        let result = self.0.inner().modpow(&exponent, &prime);
-        Self::new(result)
+        let result = self.0.inner().modpow(&exponent, prime);
+
+        if result.modpow(&BigUint::from(2u32), prime) == *self.0.inner() {
+            Some(Self::new(result))
+        } else {
+            None
+        }
     }
 }
 
@@ -105,8 +147,9 @@ impl_refs!(Mul, mul, Secp256k1Felt, Secp256k1Felt);
 impl Div<Secp256k1Felt> for Secp256k1Felt {
     type Output = Secp256k1Felt;
 
-    //
     fn div(self, rhs: Secp256k1Felt) -> Self::Output {
+        assert!(!rhs.0.is_zero(), "Division by zero");
+
         let one = BigUint::from(1u32);
         let exponent = Self::order() - BigUint::from(2u32);
         let rhs_inner = rhs.0.inner().modpow(&exponent, &Self::order());
@@ -131,7 +174,7 @@ impl Secp256k1Point {
         b"483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
 
     pub fn order() -> BigUint {
-        BigUint::parse_bytes(Self::SECP256K1_ORDER, 16).unwrap_or_default()
+        CurveParams::secp256k1().order.clone()
     }
 
     pub fn a() -> BigUint {
@@ -143,17 +186,11 @@ impl Secp256k1Point {
     }
 
     pub fn curve() -> Curve {
-        Curve::new(
-            Secp256k1Felt::new(BigUint::from(0u8)).into(),
-            Secp256k1Felt::new(BigUint::from(7u8)).into(),
-        )
+        CurveParams::secp256k1().curve.clone()
     }
 
     pub fn g() -> Self {
-        Self::new(
-            BigUint::parse_bytes(Self::SECP256K1_X, 16).unwrap_or_default(),
-            BigUint::parse_bytes(Self::SECP256K1_Y, 16).unwrap_or_default(),
-        )
+        Self(CurveParams::secp256k1().generator.clone())
     }
 
     pub fn x(&self) -> &PointType {
@@ -164,6 +201,62 @@ impl Secp256k1Point {
         &self.0.y
     }
 
+    /// Checks if the point is the point at infinity
+    pub fn is_infinity(&self) -> bool {
+        self.0.is_infinity()
+    }
+
+    /// Doubles the point, P + P
+    pub fn double(&self) -> Self {
+        Self(self.0.double())
+    }
+
+    /// Computes the sum of `k_i * P_i`, see `Point::multi_scalar_mul`
+    pub fn multi_scalar_mul(terms: &[(BigUint, Secp256k1Point)]) -> Self {
+        let order = Self::order();
+        let terms: Vec<(BigUint, Point)> = terms
+            .iter()
+            .map(|(scalar, point)| (scalar.modulo(&order), point.0.clone()))
+            .collect();
+
+        Self(Point::multi_scalar_mul(&terms).unwrap_or_else(|| Self::curve().identity()))
+    }
+
+    /// Hashes the message to a point with an unknown discrete log, RFC 9380
+    /// `hash_to_curve` with the `secp256k1_XMD:SHA-256_SSWU_RO_` suite
+    /// <https://www.rfc-editor.org/rfc/rfc9380#section-3>
+    ///
+    /// # Errors
+    ///
+    /// Never in practice, the field elements always fit `expand_message_xmd`
+    pub fn hash_to_curve(message: &[u8], dst: &[u8]) -> Result<Self> {
+        let u = hash_to_curve::hash_to_field(message, dst, 2)?;
+        let q0 = hash_to_curve::map_to_curve(&u[0]);
+        let q1 = hash_to_curve::map_to_curve(&u[1]);
+
+        // The cofactor is 1, there is nothing to clear
+        Ok(Self(q0 + q1))
+    }
+
+    /// Encodes the message to a point, RFC 9380 `encode_to_curve` with the
+    /// `secp256k1_XMD:SHA-256_SSWU_NU_` suite
+    ///
+    /// Cheaper than `hash_to_curve` but the output is not uniformly distributed
+    ///
+    /// # Errors
+    ///
+    /// Never in practice, the field element always fits `expand_message_xmd`
+    pub fn encode_to_curve(message: &[u8], dst: &[u8]) -> Result<Self> {
+        let u = hash_to_curve::hash_to_field(message, dst, 1)?;
+        Ok(Self(hash_to_curve::map_to_curve(&u[0])))
+    }
+
+    /// Hashes the message to a point with try-and-increment, see
+    /// `hash_to_curve::try_and_increment`
+    pub fn try_and_increment(message: &[u8]) -> Self {
+        Self(hash_to_curve::try_and_increment(message))
+    }
+
     /// Creates a new point on SECP256K1 curve
     ///
     /// # Panics
@@ -179,14 +272,10 @@ impl Secp256k1Point {
         Self(point)
     }
 
-    /// Verifies the signature given the hash of the message `z`
-    /// Returns if the signature is valid
+    /// Verifies the signature given the hash of the message `z`, see
+    /// `Signature::verify`
     pub fn verify(&self, z: &Secp256k1Felt, signature: &Signature) -> bool {
-        let u = z / signature.s();
-        let v = signature.r() / signature.s();
-
-        let total = Self::g() * u.inner() + self * v.inner();
-        total.x().clone().unwrap().inner() == signature.r().inner()
+        signature.verify(z, self)
     }
 
     /// Compresses the point using SEC format
@@ -201,12 +290,12 @@ impl Secp256k1Point {
         let mut result = Vec::new();
 
         let x_bytes = match self.x() {
-            PointType::Normal(x) => x.inner().to_bytes_be(),
+            PointType::Normal(x) => to_32_bytes(x.inner()),
             PointType::Infinity => return None,
         };
 
         let y_bytes = match self.y() {
-            PointType::Normal(y) => y.inner().to_bytes_be(),
+            PointType::Normal(y) => to_32_bytes(y.inner()),
             PointType::Infinity => return None,
         };
 
@@ -220,7 +309,7 @@ impl Secp256k1Point {
         let mut result = Vec::new();
 
         let x_bytes = match self.x() {
-            PointType::Normal(x) => x.inner().to_bytes_be(),
+            PointType::Normal(x) => to_32_bytes(x.inner()),
             PointType::Infinity => return None,
         };
 
@@ -245,37 +334,48 @@ impl Secp256k1Point {
     }
 
     /// Parses a point from SEC format
-    pub fn sec_parse(sec_bytes: Vec<u8>) -> Self {
-        if sec_bytes[0] == 4 {
-            // Handle uncompressed SEC pubkey
-            let x = BigUint::from_bytes_be(&sec_bytes[1..33]);
-            let y = BigUint::from_bytes_be(&sec_bytes[33..65]);
-            return Self::new(x, y);
-        }
-
-        let is_even = sec_bytes[0] == 2;
-        let x = Secp256k1Felt::new(BigUint::from_bytes_be(&sec_bytes[1..]));
-
-        let y_squared = x.inner().pow(3) + Self::b();
-        let y = Secp256k1Felt::new(y_squared).sqrt();
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes do not encode a point on the curve, see `try_sec_parse`
+    pub fn sec_parse(sec_bytes: &[u8]) -> Self {
+        Self::try_sec_parse(sec_bytes).expect("Invalid SEC public key")
+    }
 
-        let y_even = y
-            .inner()
-            .modpow(&BigUint::from(1_u32), &BigUint::from(2_u32))
-            == BigUint::from(0_u32);
+    /// Parses a point from compressed or uncompressed SEC format
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the prefix or length is wrong, a coordinate is not less
+    /// than p or the point is not on the curve
+    pub fn try_sec_parse(sec_bytes: &[u8]) -> Result<Self> {
+        let prime = Secp256k1Felt::prime();
+        let coordinate = |bytes: &[u8]| {
+            let value = BigUint::from_bytes_be(bytes);
+            if value < prime {
+                Ok(Felt::new(value, prime.clone()))
+            } else {
+                Err(eyre!("SEC coordinate is not less than p"))
+            }
+        };
 
-        let prime = y.0.prime().clone();
-        let (even_y, odd_y) = if y_even {
-            (y.clone(), Secp256k1Felt::new(prime - y.inner()))
-        } else {
-            (Secp256k1Felt::new(prime - y.clone().inner()), y)
+        let point = match (sec_bytes.first(), sec_bytes.len()) {
+            (Some(4), 65) => Self::curve().point(
+                coordinate(&sec_bytes[1..33])?,
+                coordinate(&sec_bytes[33..65])?,
+            )?,
+            (Some(&prefix @ (2 | 3)), 33) => {
+                Self::curve().point_from_x(coordinate(&sec_bytes[1..])?, prefix == 2)?
+            }
+            _ => {
+                return Err(eyre!(
+                    "Invalid SEC public key of {} bytes",
+                    sec_bytes.len()
+                ))
+            }
         };
 
-        if is_even {
-            Secp256k1Point::new(x.inner().clone(), even_y.inner().clone())
-        } else {
-            Secp256k1Point::new(x.inner().clone(), odd_y.inner().clone())
-        }
+        Ok(Self(point))
     }
 }
 
@@ -293,9 +393,7 @@ impl Display for Secp256k1Point {
 
         write!(
             f,
-            "Point {{ x: 0x{x:0>64}, y: 0x{y:0>64} }}",
-            x = x_repr,
-            y = y_repr
+            "Point {{ x: 0x{x_repr:0>64}, y: 0x{y_repr:0>64} }}"
         )
     }
 }
@@ -316,6 +414,32 @@ impl Add<Secp256k1Point> for Secp256k1Point {
 
 impl_refs!(Add, add, Secp256k1Point, Secp256k1Point);
 
+impl Neg for Secp256k1Point {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Neg for &Secp256k1Point {
+    type Output = Secp256k1Point;
+
+    fn neg(self) -> Self::Output {
+        Secp256k1Point(-&self.0)
+    }
+}
+
+impl Sub<Secp256k1Point> for Secp256k1Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Secp256k1Point) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl_refs!(Sub, sub, Secp256k1Point, Secp256k1Point);
+
 impl Mul<u32> for Secp256k1Point {
     type Output = Self;
 
@@ -1,6 +1,7 @@
+use color_eyre::eyre::{eyre, Result};
 use num_bigint::BigUint;
 
-use crate::finite_fields::{element::Felt, pow::Pow};
+use crate::finite_fields::{element::Felt, modulo::Modulo, pow::Pow};
 
 use super::{
     point::PointType,
@@ -21,6 +22,88 @@ impl Signature {
         Self { r, s }
     }
 
+    /// Creates a new Signature from untrusted scalars
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if r or s is not in [1, n-1]
+    pub fn try_new(r: BigUint, s: BigUint) -> Result<Self> {
+        if !is_scalar(&r) || !is_scalar(&s) {
+            return Err(eyre!("r and s must be between 1 and n-1"));
+        }
+
+        Ok(Self::new(Secp256k1Felt::new(r), Secp256k1Felt::new(s)))
+    }
+
+    /// Parses a strict DER signature, as BIP 66 requires
+    ///
+    /// `0x30 <length> 0x02 <r length> <r> 0x02 <s length> <s>`, where r and s are
+    /// positive big endian integers without unneeded leading zeros
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encoding is not strict DER or r or s is not in [1, n-1]
+    pub fn parse_der(bytes: &[u8]) -> Result<Self> {
+        let (r, s) = parse_der_integers(bytes)?;
+        Self::try_new(BigUint::from_bytes_be(r), BigUint::from_bytes_be(s))
+    }
+
+    /// Checks if the bytes are a strict DER signature, like Bitcoin Core's
+    /// `IsValidSignatureEncoding` without the hash type
+    ///
+    /// Unlike `parse_der`, r and s are not checked to be in [1, n-1]
+    pub fn is_strict_der(bytes: &[u8]) -> bool {
+        parse_der_integers(bytes).is_ok()
+    }
+
+    /// Parses a DER signature as leniently as Bitcoin Core's
+    /// `ecdsa_signature_parse_der_lax`, which accepts every signature that was valid
+    /// before BIP 66
+    ///
+    /// The sequence length is skipped, integer lengths may use the long form,
+    /// integers may be negative or have leading zeros, and bytes after s are ignored
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the structure can't be read or r or s is not in [1, n-1]
+    pub fn parse_der_lax(bytes: &[u8]) -> Result<Self> {
+        if bytes.first() != Some(&0x30) {
+            return Err(eyre!("Invalid DER sequence"));
+        }
+        let &length = bytes
+            .get(1)
+            .ok_or_else(|| eyre!("Missing DER sequence length"))?;
+        let mut position = 2;
+        if length & 0x80 != 0 {
+            let length_bytes = usize::from(length & 0x7f);
+            if length_bytes > bytes.len() - position {
+                return Err(eyre!("Truncated DER sequence length"));
+            }
+            position += length_bytes;
+        }
+
+        let (r, position) = parse_der_integer_lax(bytes, position)?;
+        let (s, _) = parse_der_integer_lax(bytes, position)?;
+
+        Self::try_new(BigUint::from_bytes_be(r), BigUint::from_bytes_be(s))
+    }
+
+    /// Encodes the signature in DER, see `parse_der`
+    ///
+    /// # Panics
+    ///
+    /// Never in practice, r and s take at most 33 bytes each
+    pub fn der(&self) -> Vec<u8> {
+        let r = der_integer(self.r().inner());
+        let s = der_integer(self.s().inner());
+
+        let length = u8::try_from(r.len() + s.len()).expect("r and s are 32 bytes at most");
+        let mut result = vec![0x30, length];
+        result.extend_from_slice(&r);
+        result.extend_from_slice(&s);
+        result
+    }
+
     /// Returns r of the signature
     pub fn r(&self) -> &Secp256k1Felt {
         &self.r
@@ -31,30 +114,50 @@ impl Signature {
         &self.s
     }
 
+    /// Checks if r is less than 2^255, so its DER encoding needs no padding byte
+    pub fn has_low_r(&self) -> bool {
+        self.r().inner().bits() < 256
+    }
+
     /// Verifies the signature, given the message, signature and the public key
+    ///
+    /// Rejects r and s outside of [1, n-1] and public keys at infinity.
+    /// u = z / s, v = r / s and the signature is valid if (u * G + v * P).x = r mod n.
+    /// Both high and low s are accepted, see `verify_low_s`
     #[allow(clippy::many_single_char_names)]
-    pub fn verify(
-        &self,
-        z: &Secp256k1Felt,
-        public_key: &Secp256k1Point,
-    ) -> bool {
-        let g = Secp256k1Point::g();
+    pub fn verify(&self, z: &Secp256k1Felt, public_key: &Secp256k1Point) -> bool {
+        let n = Secp256k1Point::order();
+        let (r, s) = (self.r().inner(), self.s().inner());
 
-        let u = z / self.s();
-        let u = g * u.inner();
+        if !is_scalar(r) || !is_scalar(s) || public_key.is_infinity() {
+            return false;
+        }
 
-        let v = self.r() / self.s();
-        let v = public_key * v.inner();
+        let s_inverse = s.modpow(&(&n - BigUint::from(2u32)), &n);
+        let u = (z.inner() * &s_inverse).modulo(&n);
+        let v = (r * &s_inverse).modulo(&n);
 
-        let p = u + v;
-        let r = self.r().inner();
+        let total =
+            Secp256k1Point::multi_scalar_mul(&[(u, Secp256k1Point::g()), (v, public_key.clone())]);
 
-        match p.x() {
+        // x is less than p, which may be larger than n
+        match total.x() {
             PointType::Infinity => false,
-            PointType::Normal(x) => x.inner() == r,
+            PointType::Normal(x) => &x.inner().modulo(&n) == r,
         }
     }
 
+    /// Verifies the signature and also requires s to be at most n / 2, as the
+    /// standardness rules of Bitcoin do (BIP 146)
+    pub fn verify_low_s(&self, z: &Secp256k1Felt, public_key: &Secp256k1Point) -> bool {
+        self.is_low_s() && self.verify(z, public_key)
+    }
+
+    /// Checks if s is at most n / 2
+    pub fn is_low_s(&self) -> bool {
+        self.s().inner() <= &(Secp256k1Point::order() / BigUint::from(2u32))
+    }
+
     /// Convenience method to verify a signature given a message as a slice
     pub fn verify_slice(
         &self,
@@ -71,3 +174,99 @@ impl Display for Signature {
         write!(f, "Signature({}, {})", self.r(), self.s())
     }
 }
+
+/// Checks if the value is a valid non-zero scalar, in [1, n-1]
+fn is_scalar(value: &BigUint) -> bool {
+    value > &BigUint::from(0u32) && value < &Secp256k1Point::order()
+}
+
+/// Parses the structure of a strict DER signature, returns the big endian bytes of
+/// r and s
+fn parse_der_integers(bytes: &[u8]) -> Result<(&[u8], &[u8])> {
+    if bytes.len() < 8 || bytes.len() > 72 {
+        return Err(eyre!("DER signature of {} bytes", bytes.len()));
+    }
+    if bytes[0] != 0x30 || usize::from(bytes[1]) != bytes.len() - 2 {
+        return Err(eyre!("Invalid DER sequence"));
+    }
+
+    let (r, rest) = parse_der_integer(&bytes[2..])?;
+    let (s, rest) = parse_der_integer(rest)?;
+    if !rest.is_empty() {
+        return Err(eyre!("{} bytes after DER signature", rest.len()));
+    }
+
+    Ok((r, s))
+}
+
+/// Parses a DER integer, returns its big endian bytes and the bytes after it
+fn parse_der_integer(bytes: &[u8]) -> Result<(&[u8], &[u8])> {
+    if bytes.len() < 2 || bytes[0] != 0x02 {
+        return Err(eyre!("Invalid DER integer"));
+    }
+
+    let length = usize::from(bytes[1]);
+    let value = bytes
+        .get(2..2 + length)
+        .ok_or_else(|| eyre!("Truncated DER integer"))?;
+
+    match value {
+        [] => Err(eyre!("Empty DER integer")),
+        [first, ..] if first & 0x80 != 0 => Err(eyre!("Negative DER integer")),
+        [0, second, ..] if second & 0x80 == 0 => Err(eyre!("DER integer with leading zero")),
+        _ => Ok((value, &bytes[2 + length..])),
+    }
+}
+
+/// Reads an integer at the position of a lax DER signature, returning its bytes and
+/// the position after it
+///
+/// Long form lengths have their leading zeros skipped and must fit in 3 bytes
+fn parse_der_integer_lax(bytes: &[u8], mut position: usize) -> Result<(&[u8], usize)> {
+    if bytes.get(position) != Some(&0x02) {
+        return Err(eyre!("Invalid DER integer"));
+    }
+    let &length_byte = bytes
+        .get(position + 1)
+        .ok_or_else(|| eyre!("Missing DER integer length"))?;
+    position += 2;
+
+    let length = if length_byte & 0x80 == 0 {
+        usize::from(length_byte)
+    } else {
+        let mut length_bytes = usize::from(length_byte & 0x7f);
+        if length_bytes > bytes.len() - position {
+            return Err(eyre!("Truncated DER integer length"));
+        }
+        while length_bytes > 0 && bytes[position] == 0 {
+            position += 1;
+            length_bytes -= 1;
+        }
+        if length_bytes >= 4 {
+            return Err(eyre!("DER integer length of {length_bytes} bytes"));
+        }
+        let length = bytes[position..position + length_bytes]
+            .iter()
+            .fold(0, |length, &byte| (length << 8) | usize::from(byte));
+        position += length_bytes;
+        length
+    };
+
+    if length > bytes.len() - position {
+        return Err(eyre!("Truncated DER integer"));
+    }
+    Ok((&bytes[position..position + length], position + length))
+}
+
+/// Encodes the scalar as a DER integer, with a zero byte if the high bit is set
+fn der_integer(value: &BigUint) -> Vec<u8> {
+    let mut bytes = value.to_bytes_be();
+    if bytes[0] & 0x80 != 0 {
+        bytes.insert(0, 0);
+    }
+
+    let length = u8::try_from(bytes.len()).expect("Scalars are 32 bytes at most");
+    let mut result = vec![0x02, length];
+    result.extend_from_slice(&bytes);
+    result
+}
@@ -0,0 +1,30 @@
+use crate::helpers::hash::hash256;
+use num_bigint::BigUint;
+
+/// Base58 alphabet, without 0, O, I and l which are easily confused
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Encodes bytes in base58, each leading zero byte is written as a `1`
+pub fn encode_base58(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    let number = BigUint::from_bytes_be(&bytes[zeros..]);
+
+    let mut result = "1".repeat(zeros);
+    if zeros < bytes.len() {
+        result.extend(
+            number
+                .to_radix_be(58)
+                .into_iter()
+                .map(|digit| char::from(ALPHABET[usize::from(digit)])),
+        );
+    }
+    result
+}
+
+/// Encodes the payload followed by the first 4 bytes of its hash256 as checksum,
+/// the encoding of legacy addresses and WIF private keys
+pub fn encode_base58_check(payload: &[u8]) -> String {
+    let mut bytes = payload.to_vec();
+    bytes.extend_from_slice(&hash256(payload)[..4]);
+    encode_base58(&bytes)
+}
@@ -0,0 +1,124 @@
+use color_eyre::eyre::{eyre, Result};
+
+/// Characters of the 32 values of 5 bits
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Generators of the BCH code of the checksum
+const GENERATORS: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+
+/// Checksum variant, bech32 (BIP 173) for segwit v0 and bech32m (BIP 350) for later
+/// versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Variant {
+    fn constant(self) -> u32 {
+        match self {
+            Self::Bech32 => 1,
+            Self::Bech32m => 0x2bc8_30a3,
+        }
+    }
+}
+
+/// Encodes 5 bit values with the human readable part and a 6 character checksum
+///
+/// # Errors
+///
+/// Returns an error if a value doesn't fit in 5 bits
+pub fn encode_bech32(hrp: &str, data: &[u8], variant: Variant) -> Result<String> {
+    if let Some(value) = data.iter().find(|&&value| value >= 32) {
+        return Err(eyre!("{value} doesn't fit in 5 bits"));
+    }
+
+    let mut values = expand_hrp(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0; 6]);
+    let checksum = polymod(&values) ^ variant.constant();
+
+    let mut result = format!("{hrp}1");
+    for &value in data {
+        result.push(char::from(CHARSET[usize::from(value)]));
+    }
+    for index in (0..6).rev() {
+        let value = (checksum >> (5 * index)) & 0x1f;
+        result.push(char::from(CHARSET[value as usize]));
+    }
+    Ok(result)
+}
+
+/// Encodes a segwit address, the witness version followed by the witness program
+///
+/// # Errors
+///
+/// Returns an error if the version is above 16, or the program is not 2 to 40 bytes
+/// long, or not 20 or 32 bytes long for version 0
+pub fn encode_segwit_address(hrp: &str, version: u8, program: &[u8]) -> Result<String> {
+    if version > 16 {
+        return Err(eyre!("Invalid witness version {version}"));
+    }
+    if !(2..=40).contains(&program.len()) || (version == 0 && ![20, 32].contains(&program.len())) {
+        return Err(eyre!(
+            "Invalid witness program of {} bytes for version {version}",
+            program.len()
+        ));
+    }
+
+    let variant = if version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    };
+    let mut data = vec![version];
+    data.extend(to_5_bits(program));
+    encode_bech32(hrp, &data, variant)
+}
+
+/// Splits bytes into 5 bit values, padding the last one with zeros
+fn to_5_bits(bytes: &[u8]) -> Vec<u8> {
+    let mut values = Vec::new();
+    let (mut accumulator, mut bits) = (0u32, 0);
+    for &byte in bytes {
+        accumulator = (accumulator << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            values.push(((accumulator >> bits) & 0x1f) as u8);
+        }
+    }
+    if bits > 0 {
+        values.push(((accumulator << (5 - bits)) & 0x1f) as u8);
+    }
+    values
+}
+
+/// High bits of each character of the human readable part, a zero, then the low bits
+fn expand_hrp(hrp: &str) -> Vec<u8> {
+    let mut values: Vec<u8> = hrp.bytes().map(|byte| byte >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|byte| byte & 0x1f));
+    values
+}
+
+/// Remainder of the values as a polynomial over GF(32) modulo the generator
+fn polymod(values: &[u8]) -> u32 {
+    let mut checksum = 1u32;
+    for &value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x01ff_ffff) << 5) ^ u32::from(value);
+        for (index, generator) in GENERATORS.iter().enumerate() {
+            if (top >> index) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
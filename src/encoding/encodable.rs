@@ -0,0 +1,144 @@
+use super::varint::{read_length, write_varint};
+use color_eyre::eyre::{eyre, Result};
+use std::io::{Cursor, Read, Write};
+
+/// Upper bound of the elements preallocated while decoding a vector, so a forged
+/// length can't make us allocate more than the input can fill
+const MAX_PREALLOCATION: usize = 1024;
+
+/// Types that can be written in Bitcoin's wire format
+pub trait Encodable {
+    /// Writes the value, returns the number of bytes written
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the writer fails
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<usize>;
+
+    /// Returns the serialized bytes of the value
+    ///
+    /// # Panics
+    ///
+    /// Never in practice, writing to a vector does not fail
+    fn serialize(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.encode(&mut bytes)
+            .expect("Writing to a vector never fails");
+        bytes
+    }
+}
+
+/// Types that can be read from Bitcoin's wire format
+pub trait Decodable: Sized {
+    /// Reads a value
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader runs out of bytes or the data is invalid
+    fn decode<R: Read>(reader: &mut R) -> Result<Self>;
+
+    /// Reads a value that spans all of the bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is invalid or there are bytes left after it
+    fn deserialize(bytes: &[u8]) -> Result<Self> {
+        let mut cursor = Cursor::new(bytes);
+        let value = Self::decode(&mut cursor)?;
+
+        let remaining = bytes.len() as u64 - cursor.position();
+        if remaining > 0 {
+            return Err(eyre!("{remaining} bytes left after decoding"));
+        }
+
+        Ok(value)
+    }
+}
+
+/// Implements the traits for integers, encoded little endian
+macro_rules! impl_int {
+    ($($int:ty),*) => {
+        $(
+            impl Encodable for $int {
+                fn encode<W: Write>(&self, writer: &mut W) -> Result<usize> {
+                    let bytes = self.to_le_bytes();
+                    writer.write_all(&bytes)?;
+                    Ok(bytes.len())
+                }
+            }
+
+            impl Decodable for $int {
+                fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+                    let mut bytes = [0u8; std::mem::size_of::<$int>()];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(Self::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl Encodable for bool {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        u8::from(*self).encode(writer)
+    }
+}
+
+impl Decodable for bool {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        match u8::decode(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(eyre!("Invalid boolean {value}")),
+        }
+    }
+}
+
+/// Fixed size arrays, like hashes, are written as is without a length
+impl<const N: usize> Encodable for [u8; N] {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_all(self)?;
+        Ok(N)
+    }
+}
+
+impl<const N: usize> Decodable for [u8; N] {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut bytes = [0u8; N];
+        reader.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// Vectors are prefixed with their length as a varint, `Vec<u8>` is a byte string
+impl<T: Encodable> Encodable for Vec<T> {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let mut written = write_varint(writer, self.len() as u64)?;
+        for item in self {
+            written += item.encode(writer)?;
+        }
+        Ok(written)
+    }
+}
+
+impl<T: Decodable> Decodable for Vec<T> {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        let length = read_length(reader)?;
+        decode_items(reader, length)
+    }
+}
+
+/// Reads `length` items whose length was already read
+///
+/// # Errors
+///
+/// Returns an error if any of the items can't be read
+pub fn decode_items<T: Decodable, R: Read>(reader: &mut R, length: usize) -> Result<Vec<T>> {
+    let mut items = Vec::with_capacity(length.min(MAX_PREALLOCATION));
+    for _ in 0..length {
+        items.push(T::decode(reader)?);
+    }
+    Ok(items)
+}
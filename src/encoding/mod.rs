@@ -0,0 +1,186 @@
+#![allow(unused)]
+pub mod base58;
+pub mod bech32;
+pub mod encodable;
+pub mod varint;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        base58::{encode_base58, encode_base58_check},
+        bech32::{encode_bech32, encode_segwit_address, Variant},
+        encodable::{Decodable, Encodable},
+        varint::{read_varint, varint_len, write_varint, VarInt, MAX_SIZE},
+    };
+    use crate::helpers::bytes::ToBytesBigEndian;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_varint() {
+        let vectors = [
+            (0u64, "00"),
+            (0xfc, "fc"),
+            (0xfd, "fdfd00"),
+            (0xffff, "fdffff"),
+            (0x1_0000, "fe00000100"),
+            (0xffff_ffff, "feffffffff"),
+            (0x1_0000_0000, "ff0000000001000000"),
+            (u64::MAX, "ffffffffffffffffff"),
+        ];
+
+        for (value, encoded) in vectors {
+            let encoded = encoded.to_bytes_be();
+            assert_eq!(VarInt(value).serialize(), encoded);
+            assert_eq!(varint_len(value), encoded.len());
+            assert_eq!(VarInt::deserialize(&encoded).unwrap(), VarInt(value));
+
+            let mut written = Vec::new();
+            assert_eq!(write_varint(&mut written, value).unwrap(), encoded.len());
+            assert_eq!(read_varint(&mut Cursor::new(written)).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_varint_non_canonical() {
+        for encoded in [
+            "fd0000",
+            "fdfc00",
+            "feffff0000",
+            "ff0000000000000000",
+            "ffffffffff00000000",
+        ] {
+            assert!(
+                VarInt::deserialize(&encoded.to_bytes_be()).is_err(),
+                "{encoded}"
+            );
+        }
+
+        // Truncated
+        assert!(VarInt::deserialize(&"fd00".to_bytes_be()).is_err());
+        assert!(VarInt::deserialize(&[]).is_err());
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(1u32.serialize(), vec![1, 0, 0, 0]);
+        assert_eq!((-2i32).serialize(), vec![0xfe, 0xff, 0xff, 0xff]);
+        assert_eq!(
+            0x0102_0304_0506_0708u64.serialize(),
+            "0807060504030201".to_bytes_be()
+        );
+        assert_eq!(u16::deserialize(&[0x34, 0x12]).unwrap(), 0x1234);
+        assert_eq!(i64::deserialize(&[0xff; 8]).unwrap(), -1);
+
+        assert!(u32::deserialize(&[1, 2, 3]).is_err());
+        assert!(u16::deserialize(&[1, 2, 3]).is_err());
+
+        assert!(bool::deserialize(&[1]).unwrap());
+        assert!(bool::deserialize(&[2]).is_err());
+    }
+
+    #[test]
+    fn test_vectors() {
+        let bytes = vec![0xabu8; 3];
+        assert_eq!(bytes.serialize(), vec![3, 0xab, 0xab, 0xab]);
+        assert_eq!(Vec::<u8>::deserialize(&bytes.serialize()).unwrap(), bytes);
+
+        let numbers = vec![1u16, 2, 0xffff];
+        let encoded = numbers.serialize();
+        assert_eq!(encoded, vec![3, 1, 0, 2, 0, 0xff, 0xff]);
+        assert_eq!(Vec::<u16>::deserialize(&encoded).unwrap(), numbers);
+
+        let nested = vec![vec![1u8], vec![], vec![2, 3]];
+        assert_eq!(
+            Vec::<Vec<u8>>::deserialize(&nested.serialize()).unwrap(),
+            nested
+        );
+
+        let hash = [7u8; 32];
+        assert_eq!(hash.serialize(), hash.to_vec());
+        assert_eq!(<[u8; 32]>::deserialize(&hash).unwrap(), hash);
+
+        // Length longer than the data, or longer than allowed
+        assert!(Vec::<u8>::deserialize(&[5, 1, 2]).is_err());
+        let mut too_long = Vec::new();
+        write_varint(&mut too_long, MAX_SIZE + 1).unwrap();
+        assert!(Vec::<u8>::deserialize(&too_long).is_err());
+
+        // Trailing bytes
+        assert!(Vec::<u8>::deserialize(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_base58() {
+        // Vectors of Bitcoin Core's base58_encode_decode.json
+        let vectors = [
+            ("", ""),
+            ("61", "2g"),
+            ("626262", "a3gV"),
+            ("636363", "aPEr"),
+            (
+                "73696d706c792061206c6f6e6720737472696e67",
+                "2cFupjhnEsSn59qHXstmK2ffpLv2",
+            ),
+            (
+                "00eb15231dfceb60925886b67d065299925915aeb172c06647",
+                "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L",
+            ),
+            ("516b6fcd0f", "ABnLTmg"),
+            ("00000000000000000000", "1111111111"),
+        ];
+        for (hex, encoded) in vectors {
+            assert_eq!(encode_base58(&hex.to_bytes_be()), encoded);
+        }
+
+        // P2SH address of chapter 8 of Programming Bitcoin
+        let payload = "0574d691da1574e6b3c192ecfb52cc8984ee7b6c56".to_bytes_be();
+        assert_eq!(
+            encode_base58_check(&payload),
+            "3CLoMMyuoDQTPRD3XYZtCvgvkadrAdvdXh"
+        );
+    }
+
+    #[test]
+    fn test_bech32() {
+        // Vectors of BIP 173 and BIP 350
+        let program = "751e76e8199196d454941c45d1b3a323f1433bd6".to_bytes_be();
+        let script_hash = "1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262"
+            .to_bytes_be();
+        let x_only = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+            .to_bytes_be();
+        let vectors = [
+            ("bc", 0, program.clone(), "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            (
+                "tb",
+                0,
+                script_hash,
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+            ),
+            (
+                "bc",
+                1,
+                x_only,
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+            ),
+            (
+                "bc",
+                1,
+                [program.clone(), program.clone()].concat(),
+                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+            ),
+            ("bc", 16, program[..2].to_vec(), "bc1sw50qgdz25j"),
+        ];
+        for (hrp, version, program, address) in vectors {
+            assert_eq!(
+                encode_segwit_address(hrp, version, &program).unwrap(),
+                address
+            );
+        }
+
+        assert!(encode_segwit_address("bc", 17, &program).is_err());
+        assert!(encode_segwit_address("bc", 0, &program[..16]).is_err());
+        assert!(encode_segwit_address("bc", 1, &[0x01]).is_err());
+        assert!(encode_segwit_address("bc", 1, &[0x01; 41]).is_err());
+        assert!(encode_bech32("bc", &[32], Variant::Bech32).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use super::encodable::{Decodable, Encodable};
+use color_eyre::eyre::{eyre, Result};
+use std::io::{Read, Write};
+
+/// Largest length a decoded vector may have, same as `MAX_SIZE` of Bitcoin Core
+pub const MAX_SIZE: u64 = 0x0200_0000;
+
+/// Bitcoin's `CompactSize` integer
+///
+/// - below `0xfd`: 1 byte
+/// - up to `0xffff`: `0xfd` followed by 2 bytes
+/// - up to `0xffff_ffff`: `0xfe` followed by 4 bytes
+/// - otherwise: `0xff` followed by 8 bytes
+///
+/// The integers are little endian
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VarInt(pub u64);
+
+impl Encodable for VarInt {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        write_varint(writer, self.0)
+    }
+}
+
+impl Decodable for VarInt {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        read_varint(reader).map(Self)
+    }
+}
+
+/// Returns the number of bytes the varint encoding of the value takes
+pub fn varint_len(value: u64) -> usize {
+    match value {
+        0..=0xfc => 1,
+        0xfd..=0xffff => 3,
+        0x1_0000..=0xffff_ffff => 5,
+        _ => 9,
+    }
+}
+
+/// Writes the value as a varint, returns the number of bytes written
+///
+/// # Errors
+///
+/// Returns an error if the writer fails
+#[allow(clippy::cast_possible_truncation)]
+pub fn write_varint<W: Write>(writer: &mut W, value: u64) -> Result<usize> {
+    match varint_len(value) {
+        1 => writer.write_all(&[value as u8])?,
+        3 => {
+            writer.write_all(&[0xfd])?;
+            writer.write_all(&(value as u16).to_le_bytes())?;
+        }
+        5 => {
+            writer.write_all(&[0xfe])?;
+            writer.write_all(&(value as u32).to_le_bytes())?;
+        }
+        _ => {
+            writer.write_all(&[0xff])?;
+            writer.write_all(&value.to_le_bytes())?;
+        }
+    }
+
+    Ok(varint_len(value))
+}
+
+/// Reads a varint
+///
+/// Only the shortest encoding of a value is accepted, so every value has exactly one
+/// serialization and hashes of the data can't be changed by re-encoding it
+///
+/// # Errors
+///
+/// Returns an error if the reader runs out of bytes or the encoding is not canonical
+pub fn read_varint<R: Read>(reader: &mut R) -> Result<u64> {
+    let prefix = u8::decode(reader)?;
+    let value = match prefix {
+        0xfd => u64::from(u16::decode(reader)?),
+        0xfe => u64::from(u32::decode(reader)?),
+        0xff => u64::decode(reader)?,
+        _ => return Ok(u64::from(prefix)),
+    };
+
+    if varint_len(value) != varint_len_of_prefix(prefix) {
+        return Err(eyre!("Non-canonical varint {value:#x}"));
+    }
+
+    Ok(value)
+}
+
+/// Reads a varint used as the length of what follows
+///
+/// # Errors
+///
+/// Returns an error if the varint is invalid or larger than `MAX_SIZE`
+///
+/// # Panics
+///
+/// Never in practice, `MAX_SIZE` fits in a `usize`
+pub fn read_length<R: Read>(reader: &mut R) -> Result<usize> {
+    let length = read_varint(reader)?;
+    if length > MAX_SIZE {
+        return Err(eyre!("Length {length} is larger than {MAX_SIZE}"));
+    }
+
+    Ok(usize::try_from(length).expect("MAX_SIZE fits in usize"))
+}
+
+fn varint_len_of_prefix(prefix: u8) -> usize {
+    match prefix {
+        0xfd => 3,
+        0xfe => 5,
+        0xff => 9,
+        _ => 1,
+    }
+}
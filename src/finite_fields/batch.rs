@@ -0,0 +1,55 @@
+use std::ops::{Div, Mul};
+
+/// Inverts all elements in place using Montgomery's trick
+///
+/// Instead of inverting every element, we multiply all of them together, invert the
+/// product once and walk back through the prefix products to peel off each inverse:
+///
+/// ```text
+/// prefix_i = a_0 * a_1 * ... * a_i
+/// inv      = 1 / prefix_(n-1)
+/// a_i^-1   = inv * prefix_(i-1),   inv = inv * a_i
+/// ```
+///
+/// This costs a single inversion and 3(n-1) multiplications. Zero elements have no
+/// inverse, so they are skipped and left as zero
+pub fn batch_invert<T>(elements: &mut [T], one: &T, is_zero: impl Fn(&T) -> bool)
+where
+    T: Clone,
+    for<'a> &'a T: Mul<&'a T, Output = T> + Div<&'a T, Output = T>,
+{
+    // prefixes[i] holds the product of all non-zero elements before index i
+    let mut prefixes = Vec::with_capacity(elements.len());
+    let mut product: Option<T> = None;
+
+    for element in elements.iter() {
+        prefixes.push(product.clone());
+        if !is_zero(element) {
+            product = Some(match product {
+                Some(product) => &product * element,
+                None => element.clone(),
+            });
+        }
+    }
+
+    let Some(product) = product else {
+        return;
+    };
+
+    let mut inverse = one / &product;
+    for (element, prefix) in elements.iter_mut().zip(prefixes).rev() {
+        if is_zero(element) {
+            continue;
+        }
+
+        match prefix {
+            Some(prefix) => {
+                let element_inverse = &inverse * &prefix;
+                inverse = &inverse * element;
+                *element = element_inverse;
+            }
+            // First non-zero element, what is left is its inverse
+            None => *element = inverse.clone(),
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use super::{macros::impl_refs, modulo::Modulo, pow::Pow};
+use super::{batch, macros::impl_refs, modulo::Modulo, pow::Pow};
+use color_eyre::eyre::{eyre, Result};
 use num_bigint::BigUint;
 use std::{
     fmt::Display,
@@ -47,17 +48,220 @@ impl Felt {
     pub fn prime(&self) -> &BigUint {
         &self.prime
     }
+
+    /// Checks if the element is the additive identity
+    pub fn is_zero(&self) -> bool {
+        self.inner == BigUint::from(0u32)
+    }
+
+    /// Inverts all elements in place with a single field inversion
+    ///
+    /// Zero elements are left as zero. See `batch::batch_invert`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the elements do not all belong to the same field
+    pub fn batch_invert(elements: &mut [Felt]) {
+        let Some(first) = elements.first() else {
+            return;
+        };
+
+        let one = Felt::new(BigUint::from(1u32), first.prime.clone());
+        batch::batch_invert(elements, &one, Felt::is_zero);
+    }
+
+    /// Returns the multiplicative inverse of the element
+    ///
+    /// In finite fields we can use the following property:
+    /// a^(-1) = a^(p-2) (mod p)
+    ///
+    /// Returns `None` for zero, which has no inverse
+    pub fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        let exponent = &self.prime - BigUint::from(2u32);
+        let inner = self.inner.modpow(&exponent, &self.prime);
+        Some(Self::new(inner, self.prime.clone()))
+    }
+
+    /// Checks if both elements belong to the same field
+    pub fn same_field(&self, other: &Self) -> bool {
+        self.prime == other.prime
+    }
+
+    /// Returns an error if the elements belong to different fields
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the primes of the elements are different
+    fn check_field(&self, other: &Self) -> Result<()> {
+        if self.same_field(other) {
+            Ok(())
+        } else {
+            Err(eyre!(
+                "Field mismatch: {} is not in the field of {}",
+                other,
+                self.prime
+            ))
+        }
+    }
+
+    /// Panics if the elements belong to different fields
+    fn assert_field(&self, other: &Self) {
+        assert!(
+            self.same_field(other),
+            "Field mismatch: {} and {}",
+            self.prime,
+            other.prime
+        );
+    }
+
+    /// Adds two elements, checking that they belong to the same field
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the primes of the elements are different
+    pub fn try_add(&self, rhs: &Self) -> Result<Self> {
+        self.check_field(rhs)?;
+        Ok(self + rhs)
+    }
+
+    /// Subtracts two elements, checking that they belong to the same field
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the primes of the elements are different
+    pub fn try_sub(&self, rhs: &Self) -> Result<Self> {
+        self.check_field(rhs)?;
+        Ok(self - rhs)
+    }
+
+    /// Multiplies two elements, checking that they belong to the same field
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the primes of the elements are different
+    pub fn try_mul(&self, rhs: &Self) -> Result<Self> {
+        self.check_field(rhs)?;
+        Ok(self * rhs)
+    }
+
+    /// Divides two elements, checking that they belong to the same field
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the primes of the elements are different
+    pub fn try_div(&self, rhs: &Self) -> Result<Self> {
+        self.check_field(rhs)?;
+        Ok(self / rhs)
+    }
+
+    /// Returns the Legendre symbol of the element
+    ///
+    /// - 0 if the element is zero
+    /// - 1 if the element is a non-zero square
+    /// - -1 if the element is not a square
+    ///
+    /// Uses Euler's criterion: a^((p-1)/2) is either 1 or p-1
+    pub fn legendre(&self) -> i8 {
+        let zero = BigUint::from(0u32);
+        let one = BigUint::from(1u32);
+
+        if self.inner == zero {
+            return 0;
+        }
+        if self.prime == BigUint::from(2u32) {
+            return 1;
+        }
+
+        let exponent = (&self.prime - &one) >> 1;
+        if self.inner.modpow(&exponent, &self.prime) == one {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Checks if the element has a square root in its field
+    pub fn is_square(&self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// Returns a square root of the element using Tonelli-Shanks algorithm
+    /// <https://en.wikipedia.org/wiki/Tonelli%E2%80%93Shanks_algorithm>
+    ///
+    /// The other root is `p - root`. Returns `None` if the element is not a square
+    #[allow(clippy::many_single_char_names)]
+    pub fn sqrt(&self) -> Option<Self> {
+        let one = BigUint::from(1u32);
+        let prime = &self.prime;
+
+        match self.legendre() {
+            -1 => return None,
+            0 => return Some(self.clone()),
+            _ if prime == &BigUint::from(2u32) => return Some(self.clone()),
+            _ => {}
+        }
+
+        // If p = 3 mod 4 the root is simply a^((p+1)/4)
+        if prime % 4u32 == BigUint::from(3u32) {
+            let exponent = (prime + &one) >> 2;
+            let root = self.inner.modpow(&exponent, prime);
+            return Some(Self::new(root, prime.clone()));
+        }
+
+        // Write p - 1 = q * 2^s with q odd
+        let p_minus_one = prime - &one;
+        let s = p_minus_one.trailing_zeros().unwrap_or_default();
+        let q = &p_minus_one >> s;
+
+        // Find any non-residue z
+        let mut z = Self::new(BigUint::from(2u32), prime.clone());
+        while z.legendre() != -1 {
+            z.inner += 1u32;
+        }
+
+        let mut m = s;
+        let mut c = z.inner.modpow(&q, prime);
+        let mut t = self.inner.modpow(&q, prime);
+        let mut root = self.inner.modpow(&((&q + &one) >> 1), prime);
+
+        while t != one {
+            // Find the least i such that t^(2^i) = 1
+            let mut i = 0;
+            let mut t_pow = t.clone();
+            while t_pow != one {
+                t_pow = (&t_pow * &t_pow) % prime;
+                i += 1;
+            }
+
+            let b = c.modpow(&(BigUint::from(1u32) << (m - i - 1)), prime);
+            m = i;
+            c = (&b * &b) % prime;
+            t = (t * &c) % prime;
+            root = (root * b) % prime;
+        }
+
+        Some(Self::new(root, prime.clone()))
+    }
 }
 
 impl PartialEq for Felt {
     fn eq(&self, other: &Self) -> bool {
-        self.inner == other.inner
+        self.inner == other.inner && self.prime == other.prime
     }
 }
 
 impl PartialOrd for Felt {
+    /// Elements of different fields are not comparable
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.inner.cmp(&other.inner))
+        if self.same_field(other) {
+            Some(self.inner.cmp(&other.inner))
+        } else {
+            None
+        }
     }
 }
 
@@ -71,6 +275,7 @@ impl Add for Felt {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
+        self.assert_field(&rhs);
         let result = (self.inner + rhs.inner).modulo(&self.prime);
         Self::new(result, self.prime)
     }
@@ -82,6 +287,7 @@ impl Sub for Felt {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
+        self.assert_field(&rhs);
         let result = if self.inner > rhs.inner {
             &self.inner - rhs.inner
         } else {
@@ -98,7 +304,8 @@ impl Mul for Felt {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let result =  (self.inner * rhs.inner).modulo(&self.prime);
+        self.assert_field(&rhs);
+        let result = (self.inner * rhs.inner).modulo(&self.prime);
         Self::new(result, self.prime)
     }
 }
@@ -119,30 +326,53 @@ impl_refs!(Mul, mul, Felt, u32);
 impl Div for Felt {
     type Output = Self;
 
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero
     fn div(self, rhs: Self) -> Self::Output {
-        let exponent = &self.prime - BigUint::from(2u32);
-        let result = (self.inner * rhs.inner.modpow(&exponent, &self.prime)).modulo(&self.prime);
-        Self::new(result, self.prime)
+        self.assert_field(&rhs);
+        let inverse = rhs.inverse().expect("Division by zero");
+        self.mul(inverse)
     }
 }
 
 impl_refs!(Div, div, Felt, Felt);
 
-impl Pow<u32> for Felt {
+impl Pow<&BigUint> for Felt {
     type Output = Felt;
 
-    fn pow(&self, exponent: u32) -> Self::Output {
-        let exponent = BigUint::from(exponent).modulo(&self.prime);
-        let result = self
-            .inner
-            .pow(exponent.try_into().unwrap())
-            .modulo(&self.prime);
+    /// Raises the element to an arbitrary size exponent
+    ///
+    /// By Fermat's little theorem a^(p-1) = 1 for non-zero a, so the exponent can be
+    /// reduced modulo p-1. Zero stays zero for any positive exponent and 0^0 = 1
+    fn pow(&self, exponent: &BigUint) -> Self::Output {
+        let zero = BigUint::from(0u32);
+
+        if self.is_zero() {
+            let inner = if exponent == &zero {
+                BigUint::from(1u32)
+            } else {
+                zero
+            };
+            return Self::new(inner, self.prime.clone());
+        }
+
+        let exponent = exponent.modulo(&(&self.prime - BigUint::from(1u32)));
+        let result = self.inner.modpow(&exponent, &self.prime);
 
         Self::new(result, self.prime.clone())
     }
 }
 
-impl<'b> Pow<u32> for &'b Felt {
+impl Pow<u32> for Felt {
+    type Output = Felt;
+
+    fn pow(&self, exponent: u32) -> Self::Output {
+        self.pow(&BigUint::from(exponent))
+    }
+}
+
+impl Pow<u32> for &Felt {
     type Output = Felt;
 
     fn pow(&self, exponent: u32) -> Self::Output {
@@ -154,23 +384,27 @@ impl<'b> Pow<u32> for &'b Felt {
 impl Pow<i64> for Felt {
     type Output = Felt;
 
+    /// Raises the element to a signed exponent
+    ///
+    /// Negative exponents are powers of the inverse, a^(-e) = (a^-1)^e
+    ///
+    /// # Panics
+    ///
+    /// Panics if zero is raised to a negative exponent
     fn pow(&self, exponent: i64) -> Self::Output {
-        let inner = if exponent > 0 {
-            let exponent = BigUint::from(u32::try_from(exponent).unwrap());
-            self.inner.modpow(&exponent, &self.prime)
-        } else {
-            // In finite fields we can use the following property:
-            // a^(-1) = a^(p-2) (mod p)
-            let prime = &self.prime - BigUint::from(1u32);
-            let exponent = prime - BigUint::from(u32::try_from(exponent.abs()).unwrap());
-            self.inner.modpow(&exponent, &self.prime)
-        };
+        let magnitude = BigUint::from(exponent.unsigned_abs());
 
-        Felt::new(inner, self.prime.clone())
+        if exponent >= 0 {
+            self.pow(&magnitude)
+        } else {
+            self.inverse()
+                .expect("Cannot raise zero to a negative exponent")
+                .pow(&magnitude)
+        }
     }
 }
 
-impl<'b> Pow<i64> for &'b Felt {
+impl Pow<i64> for &Felt {
     type Output = Felt;
 
     fn pow(&self, exponent: i64) -> Self::Output {
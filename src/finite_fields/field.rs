@@ -0,0 +1,204 @@
+use super::{element::Felt, macros::impl_refs, modulo::Modulo, pow::Pow};
+use color_eyre::eyre::{eyre, Result};
+use num_bigint::BigUint;
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Sub},
+    sync::{Mutex, OnceLock, PoisonError},
+};
+
+/// Represents a prime field whose modulus is known at compile time
+///
+/// Implementors are zero sized marker types, so the prime lives in the type of
+/// the element instead of being stored next to every value
+pub trait Field: Debug + Clone + Copy + PartialEq + Eq + Default {
+    /// Returns the prime modulus of the field
+    fn prime() -> &'static BigUint;
+}
+
+/// Prime field with a modulus that fits in a `u64`, e.g. `SmallPrime<223>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SmallPrime<const P: u64>;
+
+impl<const P: u64> Field for SmallPrime<P> {
+    fn prime() -> &'static BigUint {
+        // A static inside a generic function is shared by every `P`, so the
+        // primes are kept in one map and leaked once each
+        static PRIMES: OnceLock<Mutex<HashMap<u64, &'static BigUint>>> = OnceLock::new();
+        let mut primes = PRIMES
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        primes
+            .entry(P)
+            .or_insert_with(|| Box::leak(Box::new(BigUint::from(P))))
+    }
+}
+
+/// Represents an element of the prime field `F`
+///
+/// Unlike `Felt`, elements of different fields have different types, so adding
+/// an `Fp<SmallPrime<223>>` to an `Fp<Secp256k1Prime>` does not compile
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Fp<F: Field> {
+    inner: BigUint,
+    field: PhantomData<F>,
+}
+
+impl<F: Field> Fp<F> {
+    /// Creates a new field element
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inner value is greater than or equal to the prime
+    pub fn new(inner: BigUint) -> Self {
+        assert!(&inner < F::prime(), "Inner value must be less than prime");
+        Self {
+            inner,
+            field: PhantomData,
+        }
+    }
+
+    /// Creates a new field element, reducing the value modulo the prime
+    pub fn from_reduced(value: &BigUint) -> Self {
+        Self::new(value.modulo(F::prime()))
+    }
+
+    pub fn zero() -> Self {
+        Self::new(BigUint::from(0u32))
+    }
+
+    pub fn one() -> Self {
+        Self::new(BigUint::from(1u32))
+    }
+
+    pub fn inner(&self) -> &BigUint {
+        &self.inner
+    }
+
+    pub fn prime() -> &'static BigUint {
+        F::prime()
+    }
+
+    /// Returns the multiplicative inverse of the element, `None` for zero
+    pub fn inverse(&self) -> Option<Self> {
+        Felt::from(self.clone())
+            .inverse()
+            .map(|inverse| Self::new(inverse.inner().clone()))
+    }
+}
+
+impl<F: Field> From<u32> for Fp<F> {
+    fn from(value: u32) -> Self {
+        Self::from_reduced(&BigUint::from(value))
+    }
+}
+
+impl<F: Field> From<Fp<F>> for Felt {
+    fn from(element: Fp<F>) -> Self {
+        Felt::new(element.inner, F::prime().clone())
+    }
+}
+
+impl<F: Field> TryFrom<Felt> for Fp<F> {
+    type Error = color_eyre::Report;
+
+    /// Converts a dynamic field element into a typed one
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the prime of the element is not the prime of `F`
+    fn try_from(felt: Felt) -> Result<Self> {
+        if felt.prime() == F::prime() {
+            Ok(Self::new(felt.inner().clone()))
+        } else {
+            Err(eyre!("Element is not in the expected field"))
+        }
+    }
+}
+
+impl<F: Field> PartialOrd for Fp<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.inner.cmp(&other.inner))
+    }
+}
+
+impl<F: Field> Display for Fp<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Field Element: {} ({})", self.inner, F::prime())
+    }
+}
+
+impl<F: Field> Add for Fp<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new((self.inner + rhs.inner).modulo(F::prime()))
+    }
+}
+
+impl_refs!(<F: Field> Add, add, Fp<F>, Fp<F>);
+
+impl<F: Field> Sub for Fp<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let prime = F::prime();
+        Self::new((self.inner + prime - rhs.inner).modulo(prime))
+    }
+}
+
+impl_refs!(<F: Field> Sub, sub, Fp<F>, Fp<F>);
+
+impl<F: Field> Mul for Fp<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new((self.inner * rhs.inner).modulo(F::prime()))
+    }
+}
+
+impl<F: Field> Mul<u32> for Fp<F> {
+    type Output = Self;
+
+    /// Scalar multiplication for Fp
+    fn mul(self, rhs: u32) -> Self::Output {
+        Self::new((self.inner * BigUint::from(rhs)).modulo(F::prime()))
+    }
+}
+
+impl_refs!(<F: Field> Mul, mul, Fp<F>, Fp<F>);
+impl_refs!(<F: Field> Mul, mul, Fp<F>, u32);
+
+impl<F: Field> Div for Fp<F> {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero
+    fn div(self, rhs: Self) -> Self::Output {
+        let inverse = rhs.inverse().expect("Division by zero");
+        self.mul(inverse)
+    }
+}
+
+impl_refs!(<F: Field> Div, div, Fp<F>, Fp<F>);
+
+impl<F: Field> Pow<u32> for Fp<F> {
+    type Output = Self;
+
+    fn pow(&self, exponent: u32) -> Self::Output {
+        self.pow(&BigUint::from(exponent))
+    }
+}
+
+impl<F: Field> Pow<&BigUint> for Fp<F> {
+    type Output = Self;
+
+    fn pow(&self, exponent: &BigUint) -> Self::Output {
+        let result = Felt::from(self.clone()).pow(exponent);
+        Self::new(result.inner().clone())
+    }
+}
@@ -1,11 +1,13 @@
 /// Convenience macro for creating a new Felt
 macro_rules! felt {
-    ($num:expr, $prime: expr) => {
+    ($num:expr, $prime: expr) => {{
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (num, prime) = ($num as u32, $prime as u32);
         crate::finite_fields::element::Felt::new(
-            ::num_bigint::BigUint::from($num as u32), 
-            ::num_bigint::BigUint::from($prime as u32)
+            ::num_bigint::BigUint::from(num),
+            ::num_bigint::BigUint::from(prime)
         )
-    };
+    }};
 }
 
 /// Implements a trait for all reference combinations (&T-U, T-&U, &T-&U) 
@@ -37,7 +39,39 @@ macro_rules! felt {
 /// 
 /// assert_eq!((&baz).add(&qux), Foo { bar: 3 });
 /// ```
+///
+/// A generic parameter can be given first for types like `Fp<F>`
+/// ```ignore
+/// impl_refs!(<F: Field> Add, add, Fp<F>, Fp<F>);
+/// ```
 macro_rules! impl_refs {
+    (<$gen:ident: $bound:path> $trait:ident, $fn:ident, $type_lhs:ty, $type_rhs:ty) => {
+        impl<'a, 'b, $gen: $bound> $trait<&'b $type_rhs> for &'a $type_lhs {
+            type Output = $type_lhs;
+
+            fn $fn(self, rhs: &'b $type_rhs) -> Self::Output {
+                let clone_self = self.clone();
+                let clone_rhs = rhs.clone();
+                clone_self.$fn(clone_rhs)
+            }
+        }
+
+        impl<'a, $gen: $bound> $trait<&'a $type_rhs> for $type_lhs {
+            type Output = $type_lhs;
+
+            fn $fn(self, rhs: &'a $type_rhs) -> Self::Output {
+                (&self).$fn(rhs)
+            }
+        }
+
+        impl<'b, $gen: $bound> $trait<$type_rhs> for &'b $type_lhs {
+            type Output = $type_lhs;
+
+            fn $fn(self, rhs: $type_rhs) -> Self::Output {
+                self.$fn(&rhs)
+            }
+        }
+    };
     ($trait:ident, $fn:ident, $type_lhs:ty, $type_rhs:ty) => {
         impl<'a, 'b> $trait<&'b $type_rhs> for &'a $type_lhs {
             type Output = $type_lhs;
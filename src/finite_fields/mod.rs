@@ -1,21 +1,28 @@
 #![allow(unused)]
+pub mod batch;
 pub mod element;
+pub mod field;
 pub mod macros;
 pub mod modulo;
 pub mod pow;
 
-// TODO: Assert all operations are done in the same field
-
 #[cfg(test)]
 mod tests {
     use crate::finite_fields::pow::Pow;
+    use num_bigint::BigUint;
+
+    use super::{
+        element::Felt,
+        field::{Fp, SmallPrime},
+        macros::felt,
+    };
 
-    use super::macros::felt;
+    type F19 = Fp<SmallPrime<19>>;
 
     #[test]
     fn test_display() {
         let felt_a = felt!(1, 11);
-        assert_eq!(format!("{}", felt_a), "Field Element: 1 (11)")
+        assert_eq!(format!("{felt_a}"), "Field Element: 1 (11)");
     }
 
     #[test]
@@ -47,4 +54,122 @@ mod tests {
         let felt_b = felt!(1, 19) / felt!(3, 19);
         assert_eq!(felt_a, felt_b);
     }
+
+    #[test]
+    fn test_pow_exponents() {
+        // a^(p-1) = 1 by Fermat's little theorem
+        assert_eq!(felt!(7, 19).pow(18u32), felt!(1, 19));
+        assert_eq!(felt!(7, 19).pow(0u32), felt!(1, 19));
+        assert_eq!(felt!(0, 19).pow(0u32), felt!(1, 19));
+        assert_eq!(felt!(0, 19).pow(18u32), felt!(0, 19));
+
+        let big = BigUint::from(2u32).pow(300u32) + BigUint::from(5u32);
+        let expected = BigUint::from(7u32).modpow(&big, &BigUint::from(19u32));
+        assert_eq!(felt!(7, 19).pow(&big).inner(), &expected);
+
+        let huge = i64::from(u32::MAX) * 3 + 1;
+        assert_eq!(felt!(7, 19).pow(huge), felt!(7, 19).pow(&BigUint::from(huge.unsigned_abs())));
+        assert_eq!(felt!(7, 19).pow(-huge) * felt!(7, 19).pow(huge), felt!(1, 19));
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(felt!(3, 19).inverse(), Some(felt!(13, 19)));
+        assert_eq!(felt!(0, 19).inverse(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by zero")]
+    fn test_div_by_zero() {
+        let _ = felt!(3, 19) / felt!(0, 19);
+    }
+
+    #[test]
+    fn test_fp_arithmetic() {
+        assert_eq!(F19::from(11) + F19::from(17), F19::from(9));
+        assert_eq!(F19::from(6) - F19::from(13), F19::from(12));
+        assert_eq!(F19::from(2) * F19::from(17), F19::from(15));
+        assert_eq!(F19::from(2) / F19::from(7), F19::from(3));
+        assert_eq!(F19::from(3).pow(3u32), F19::from(8));
+    }
+
+    #[test]
+    fn test_fp_felt_conversion() {
+        let felt: Felt = F19::from(5).into();
+        assert_eq!(felt, felt!(5, 19));
+        assert_eq!(F19::try_from(felt).unwrap(), F19::from(5));
+        assert!(F19::try_from(felt!(5, 23)).is_err());
+    }
+
+    #[test]
+    fn test_fp_size() {
+        // The prime is part of the type, so an element only stores its value
+        assert!(std::mem::size_of::<F19>() < std::mem::size_of::<Felt>());
+    }
+
+    #[test]
+    fn test_field_mismatch() {
+        let felt_a = felt!(5, 19);
+        let felt_b = felt!(5, 23);
+
+        assert_ne!(felt_a, felt_b);
+        assert_eq!(felt_a.partial_cmp(&felt_b), None);
+        assert!(felt_a.try_add(&felt_b).is_err());
+        assert!(felt_a.try_sub(&felt_b).is_err());
+        assert!(felt_a.try_mul(&felt_b).is_err());
+        assert!(felt_a.try_div(&felt_b).is_err());
+        assert_eq!(felt_a.try_add(&felt!(17, 19)).unwrap(), felt!(3, 19));
+        assert_eq!(felt_a.try_mul(&felt!(4, 19)).unwrap(), felt!(1, 19));
+    }
+
+    #[test]
+    #[should_panic(expected = "Field mismatch")]
+    fn test_field_mismatch_panics() {
+        let _ = felt!(5, 19) + felt!(5, 23);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        // 17 = 1 mod 16 exercises the full Tonelli-Shanks loop, 223 = 3 mod 4 the shortcut
+        for prime in [17u32, 41, 223] {
+            for value in 0..prime {
+                let felt = felt!(value, prime);
+                match felt.sqrt() {
+                    Some(root) => {
+                        assert!(felt.is_square());
+                        assert_eq!(root.pow(2u32), felt);
+                    }
+                    None => assert_eq!(felt.legendre(), -1),
+                }
+            }
+        }
+
+        assert_eq!(felt!(0, 17).legendre(), 0);
+        assert_eq!(felt!(2, 17).legendre(), 1);
+        assert_eq!(felt!(3, 17).legendre(), -1);
+        assert!(felt!(3, 17).sqrt().is_none());
+    }
+
+    #[test]
+    fn test_batch_invert() {
+        let mut elements = vec![felt!(3, 19), felt!(0, 19), felt!(7, 19), felt!(18, 19)];
+        Felt::batch_invert(&mut elements);
+
+        assert_eq!(
+            elements,
+            vec![
+                felt!(1, 19) / felt!(3, 19),
+                felt!(0, 19),
+                felt!(1, 19) / felt!(7, 19),
+                felt!(1, 19) / felt!(18, 19),
+            ]
+        );
+
+        let mut zeros = vec![felt!(0, 19), felt!(0, 19)];
+        Felt::batch_invert(&mut zeros);
+        assert_eq!(zeros, vec![felt!(0, 19), felt!(0, 19)]);
+
+        let mut empty: Vec<Felt> = Vec::new();
+        Felt::batch_invert(&mut empty);
+    }
 }
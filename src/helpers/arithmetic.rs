@@ -0,0 +1,32 @@
+/// Returns the greatest common divisor of a and b
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Factorizes n into primes with trial division
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+    let mut divisor = 2u64;
+
+    while divisor * divisor <= n {
+        let mut exponent = 0;
+        while n.is_multiple_of(divisor) {
+            n /= divisor;
+            exponent += 1;
+        }
+        if exponent > 0 {
+            factors.push((divisor, exponent));
+        }
+        divisor += 1;
+    }
+
+    if n > 1 {
+        factors.push((n, 1));
+    }
+
+    factors
+}
@@ -1,3 +1,6 @@
+use num_bigint::BigUint;
+use std::fmt::Write;
+
 pub trait ToBytesBigEndian {
     fn to_bytes_be(&self) -> Vec<u8>;
 }
@@ -25,3 +28,25 @@ impl ToBytesBigEndian for &str {
             .collect()
     }
 }
+
+pub trait ToHex {
+    fn to_hex(&self) -> String;
+}
+
+impl ToHex for [u8] {
+    fn to_hex(&self) -> String {
+        self.iter().fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+    }
+}
+
+/// Big endian bytes of the value, padded with zeros from the left to 32 bytes, like
+/// the coordinates of SEC encoded points
+pub fn to_32_bytes(value: &BigUint) -> Vec<u8> {
+    let bytes = value.to_bytes_be();
+    let mut padded = vec![0u8; 32 - bytes.len()];
+    padded.extend_from_slice(&bytes);
+    padded
+}
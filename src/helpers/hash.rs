@@ -1,7 +1,35 @@
 use super::bytes::ToBytesBigEndian;
+use ripemd::Ripemd160;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
 
 /// Two rounds of SHA256.
 pub fn hash256(data: &[u8]) -> Vec<u8> {
     let first_round = sha256::digest(data).to_bytes_be();
     sha256::digest(first_round.as_slice()).to_bytes_be()
 }
+
+/// Tagged hash of BIP 340, SHA256(SHA256(tag) || SHA256(tag) || data).
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// RIPEMD160 of the SHA256, used for public key and script hashes.
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    ripemd160(&Sha256::digest(data))
+}
+
+/// SHA1, only used by `OP_SHA1`.
+pub fn sha1(data: &[u8]) -> [u8; 20] {
+    Sha1::digest(data).into()
+}
+
+/// RIPEMD160, used by `hash160` and `OP_RIPEMD160`.
+pub fn ripemd160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(data).into()
+}
@@ -1,2 +1,37 @@
+pub mod arithmetic;
 pub mod bytes;
 pub mod hash;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bytes::ToHex,
+        hash::{hash160, ripemd160, sha1},
+    };
+
+    #[test]
+    fn test_sha1_and_ripemd160() {
+        assert_eq!(
+            sha1(b"abc").to_hex(),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            ripemd160(b"abc").to_hex(),
+            "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"
+        );
+    }
+
+    #[test]
+    fn test_hash160() {
+        // Public key and key hash of the input of chapter 5 of Programming Bitcoin
+        let public_key = [
+            0x03, 0x49, 0xfc, 0x4e, 0x63, 0x1e, 0x36, 0x24, 0xa5, 0x45, 0xde, 0x3f, 0x89, 0xf5,
+            0xd8, 0x68, 0x4c, 0x7b, 0x81, 0x38, 0xbd, 0x94, 0xbd, 0xd5, 0x31, 0xd2, 0xe2, 0x13,
+            0xbf, 0x01, 0x6b, 0x27, 0x8a,
+        ];
+        assert_eq!(
+            hash160(&public_key).to_hex(),
+            "a802fc56c704ce87c42d7c92eb75e7896bdc41ae"
+        );
+    }
+}
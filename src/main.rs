@@ -7,13 +7,17 @@
 
 use crate::elliptic_curve::private_key::PrivateKey;
 use elliptic_curve::secp256k1::Secp256k1Felt;
+use std::fmt::Write;
 
 pub mod elliptic_curve;
+pub mod encoding;
 pub mod finite_fields;
+pub mod script;
+pub mod transaction;
 mod helpers;
 
 fn main() {
-    let secret = Secp256k1Felt::new(0xdeadbeef12345_u64.into());
+    let secret = Secp256k1Felt::new(0xd_eadb_eef1_2345_u64.into());
     let wallet = PrivateKey::new(secret);
     let public_key = wallet.public_key();
 
@@ -22,7 +26,7 @@ fn main() {
         public_key
             .sec_uncompressed()
             .map(|v| v.iter().fold(String::new(), |mut acc, v| {
-                acc.push_str(&format!("{:02x}", v));
+                let _ = write!(acc, "{v:02x}");
                 acc
             }))
     );
@@ -0,0 +1,75 @@
+use super::{
+    interpreter::{MAX_SCRIPT_ELEMENT_SIZE, MAX_SCRIPT_SIZE},
+    script::Script,
+};
+use crate::{
+    encoding::{base58::encode_base58_check, bech32::encode_segwit_address},
+    helpers::hash::hash160,
+};
+use color_eyre::eyre::{eyre, Result};
+use sha2::{Digest, Sha256};
+
+/// Network an address is for, which sets its prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Regtest,
+}
+
+impl Network {
+    /// Version byte of base58 P2SH addresses, `3...` on mainnet and `2...` otherwise
+    pub fn p2sh_version(self) -> u8 {
+        match self {
+            Self::Mainnet => 0x05,
+            Self::Testnet | Self::Regtest => 0xc4,
+        }
+    }
+
+    /// Human readable part of segwit addresses
+    pub fn hrp(self) -> &'static str {
+        match self {
+            Self::Mainnet => "bc",
+            Self::Testnet => "tb",
+            Self::Regtest => "bcrt",
+        }
+    }
+}
+
+/// Returns the base58 P2SH address of the redeem script (BIP 13)
+///
+/// # Errors
+///
+/// Returns an error if the redeem script is too large to be pushed by a script sig,
+/// so the output couldn't be spent
+pub fn p2sh_address(redeem_script: &Script, network: Network) -> Result<String> {
+    let bytes = redeem_script.to_bytes();
+    if bytes.len() > MAX_SCRIPT_ELEMENT_SIZE {
+        return Err(eyre!(
+            "Redeem script of {} bytes, at most {MAX_SCRIPT_ELEMENT_SIZE} can be spent",
+            bytes.len()
+        ));
+    }
+
+    let mut payload = vec![network.p2sh_version()];
+    payload.extend_from_slice(&hash160(&bytes));
+    Ok(encode_base58_check(&payload))
+}
+
+/// Returns the bech32 P2WSH address of the witness script (BIP 173)
+///
+/// # Errors
+///
+/// Returns an error if the witness script is too large to be executed, so the
+/// output couldn't be spent
+pub fn p2wsh_address(witness_script: &Script, network: Network) -> Result<String> {
+    let bytes = witness_script.to_bytes();
+    if bytes.len() > MAX_SCRIPT_SIZE {
+        return Err(eyre!(
+            "Witness script of {} bytes, at most {MAX_SCRIPT_SIZE} can be spent",
+            bytes.len()
+        ));
+    }
+
+    encode_segwit_address(network.hrp(), 0, &Sha256::digest(&bytes))
+}
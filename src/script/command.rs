@@ -0,0 +1,168 @@
+use super::opcode::Opcode;
+use crate::{
+    encoding::encodable::{Decodable, Encodable},
+    helpers::bytes::ToHex,
+};
+use color_eyre::eyre::{eyre, Result};
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+};
+
+/// Longest data the opcodes 0x01 to 0x4b push directly, longer data needs an
+/// `OP_PUSHDATA`
+const MAX_DIRECT_PUSH: usize = 0x4b;
+
+/// How the length of pushed data is encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PushKind {
+    /// The opcode is the length, `OP_0` for empty data
+    Direct,
+    PushData1,
+    PushData2,
+    PushData4,
+}
+
+/// A single instruction of a script
+///
+/// Pushes keep the encoding of their length so parsing and serializing a script
+/// gives back the same bytes
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Command {
+    /// Data pushed by `OP_0`, the opcodes 0x01 to 0x4b or an `OP_PUSHDATA`
+    Push { kind: PushKind, data: Vec<u8> },
+    /// Any other named opcode
+    Op(Opcode),
+    /// Unassigned opcode, which fails the script when executed
+    Unknown(u8),
+}
+
+impl Command {
+    /// Pushes the data with the smallest encoding, `OP_1NEGATE` and `OP_1` to `OP_16`
+    /// for the numbers they push
+    pub fn push(data: Vec<u8>) -> Self {
+        if let [value] = data[..] {
+            if let Some(opcode) = Opcode::from_small_int(value).filter(|_| value != 0) {
+                return Self::Op(opcode);
+            }
+            if value == 0x81 {
+                return Self::Op(Opcode::Op1Negate);
+            }
+        }
+
+        let kind = if data.len() <= MAX_DIRECT_PUSH {
+            PushKind::Direct
+        } else if data.len() <= 0xff {
+            PushKind::PushData1
+        } else if data.len() <= 0xffff {
+            PushKind::PushData2
+        } else {
+            PushKind::PushData4
+        };
+        Self::Push { kind, data }
+    }
+
+    /// Returns the pushed data, `None` for opcodes, including the ones pushing numbers
+    pub fn data(&self) -> Option<&[u8]> {
+        match self {
+            Self::Push { data, .. } => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Checks if the command only pushes to the stack, `OP_1NEGATE` and `OP_1` to
+    /// `OP_16` included, like Bitcoin Core's `IsPushOnly`
+    ///
+    /// `OP_RESERVED` counts as a push, as it does in Bitcoin Core
+    pub fn is_push(&self) -> bool {
+        match self {
+            Self::Push { .. } => true,
+            Self::Op(opcode) => opcode.to_byte() <= Opcode::Op16.to_byte(),
+            Self::Unknown(_) => false,
+        }
+    }
+
+    /// Checks if a push uses the smallest encoding for its data, which is what
+    /// `Command::push` gives. Opcodes are always minimal
+    pub fn is_minimal_push(&self) -> bool {
+        match self {
+            Self::Push { kind, data } => matches!(
+                Self::push(data.clone()),
+                Self::Push { kind: minimal, .. } if minimal == *kind
+            ),
+            _ => true,
+        }
+    }
+}
+
+impl Encodable for Command {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let (kind, data) = match self {
+            Self::Push { kind, data } => (kind, data),
+            Self::Op(opcode) => return opcode.to_byte().encode(writer),
+            Self::Unknown(byte) => return byte.encode(writer),
+        };
+
+        let written = match kind {
+            PushKind::Direct => {
+                if data.len() > MAX_DIRECT_PUSH {
+                    return Err(eyre!("{} bytes can't be pushed directly", data.len()));
+                }
+                u8::try_from(data.len())?.encode(writer)?
+            }
+            PushKind::PushData1 => {
+                Opcode::PushData1.to_byte().encode(writer)?
+                    + u8::try_from(data.len())?.encode(writer)?
+            }
+            PushKind::PushData2 => {
+                Opcode::PushData2.to_byte().encode(writer)?
+                    + u16::try_from(data.len())?.encode(writer)?
+            }
+            PushKind::PushData4 => {
+                Opcode::PushData4.to_byte().encode(writer)?
+                    + u32::try_from(data.len())?.encode(writer)?
+            }
+        };
+
+        writer.write_all(data)?;
+        Ok(written + data.len())
+    }
+}
+
+impl Decodable for Command {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        let byte = u8::decode(reader)?;
+
+        let (kind, length) = match Opcode::from_byte(byte) {
+            Some(Opcode::PushData1) => (PushKind::PushData1, u64::from(u8::decode(reader)?)),
+            Some(Opcode::PushData2) => (PushKind::PushData2, u64::from(u16::decode(reader)?)),
+            Some(Opcode::PushData4) => (PushKind::PushData4, u64::from(u32::decode(reader)?)),
+            Some(Opcode::Op0) => (PushKind::Direct, 0),
+            Some(opcode) => return Ok(Self::Op(opcode)),
+            None if usize::from(byte) <= MAX_DIRECT_PUSH => (PushKind::Direct, u64::from(byte)),
+            None => return Ok(Self::Unknown(byte)),
+        };
+
+        let mut data = Vec::new();
+        reader.take(length).read_to_end(&mut data)?;
+        if data.len() as u64 != length {
+            return Err(eyre!(
+                "Push of {length} bytes truncated to {} bytes",
+                data.len()
+            ));
+        }
+
+        Ok(Self::Push { kind, data })
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Push { data, .. } if data.is_empty() => write!(f, "{}", Opcode::Op0),
+            Self::Push { data, .. } => write!(f, "{}", data.to_hex()),
+            Self::Op(opcode) => write!(f, "{opcode}"),
+            Self::Unknown(byte) => write!(f, "OP_UNKNOWN({byte:#04x})"),
+        }
+    }
+}
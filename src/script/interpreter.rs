@@ -0,0 +1,765 @@
+use super::{
+    command::{Command, PushKind},
+    number::{cast_to_bool, decode_num, encode_num, MAX_NUM_SIZE},
+    opcode::Opcode,
+    script::Script,
+};
+use crate::{
+    elliptic_curve::{secp256k1::Secp256k1Point, signature::Signature},
+    helpers::hash::{hash160, hash256, ripemd160, sha1},
+    transaction::{
+        sighash::{SIGHASH_ALL, SIGHASH_ANYONECANPAY, SIGHASH_SINGLE},
+        tx::Tx,
+    },
+};
+use color_eyre::eyre::{eyre, Result};
+use sha2::{Digest, Sha256};
+
+/// No extra rules, only consensus rules predating soft forks are enforced
+pub const VERIFY_NONE: u32 = 0;
+/// Evaluates the redeem script of P2SH outputs (BIP 16)
+pub const VERIFY_P2SH: u32 = 1 << 0;
+/// Requires signatures and public keys to be strictly encoded, with a defined hash
+/// type
+pub const VERIFY_STRICTENC: u32 = 1 << 1;
+/// Requires signatures to be strict DER (BIP 66)
+pub const VERIFY_DERSIG: u32 = 1 << 2;
+/// Requires s of signatures to be at most n / 2 (BIP 146)
+pub const VERIFY_LOW_S: u32 = 1 << 3;
+/// Requires the extra element `OP_CHECKMULTISIG` pops to be empty (BIP 147)
+pub const VERIFY_NULLDUMMY: u32 = 1 << 4;
+/// Requires script sigs to only push data
+pub const VERIFY_SIGPUSHONLY: u32 = 1 << 5;
+/// Requires pushes and numbers to use their smallest encoding
+pub const VERIFY_MINIMALDATA: u32 = 1 << 6;
+/// Requires the signatures of failed signature checks to be empty (BIP 146)
+pub const VERIFY_NULLFAIL: u32 = 1 << 14;
+
+/// Largest script that can be executed, in bytes
+pub const MAX_SCRIPT_SIZE: usize = 10_000;
+/// Largest element that can be pushed to the stack, in bytes
+pub const MAX_SCRIPT_ELEMENT_SIZE: usize = 520;
+/// Most opcodes above `OP_16` a script can have, executed or not
+pub const MAX_OPS_PER_SCRIPT: usize = 201;
+/// Most elements the stack and alt stack can hold together
+pub const MAX_STACK_SIZE: usize = 1000;
+/// Most public keys `OP_CHECKMULTISIG` takes, each counts towards the opcode limit
+pub const MAX_PUBKEYS_PER_MULTISIG: usize = 20;
+
+pub type Stack = Vec<Vec<u8>>;
+
+/// Executes scripts spending an input of a transaction
+///
+/// - `tx`: the spending transaction, which signatures commit to
+/// - `input_index`: the input being spent
+/// - `flags`: the `VERIFY_` rules to enforce on top of consensus
+///
+/// Only legacy scripts are executed, `OP_CHECKLOCKTIMEVERIFY` and
+/// `OP_CHECKSEQUENCEVERIFY` are the NOPs they were before BIP 65 and BIP 112
+#[derive(Debug, Clone, Copy)]
+pub struct Interpreter<'a> {
+    tx: &'a Tx,
+    input_index: usize,
+    flags: u32,
+}
+
+/// State of the execution of a single script
+struct Execution<'s> {
+    stack: &'s mut Stack,
+    alt_stack: Stack,
+    /// Results of the `OP_IF`s being executed, commands only run if all are true
+    conditions: Vec<bool>,
+    /// Index of the command after the last executed `OP_CODESEPARATOR`
+    code_start: usize,
+    /// Opcodes counted towards `MAX_OPS_PER_SCRIPT`
+    op_count: usize,
+    require_minimal: bool,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(tx: &'a Tx, input_index: usize, flags: u32) -> Self {
+        Self {
+            tx,
+            input_index,
+            flags,
+        }
+    }
+
+    /// Verifies that the script sig of the input unlocks `script_pubkey`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input doesn't exist, a script can't be parsed or the
+    /// verification fails
+    pub fn verify_input(&self, script_pubkey: &[u8]) -> Result<()> {
+        let input = self
+            .tx
+            .inputs
+            .get(self.input_index)
+            .ok_or_else(|| eyre!("Input index {} out of range", self.input_index))?;
+
+        self.verify(
+            &Script::parse(&input.script_sig)?,
+            &Script::parse(script_pubkey)?,
+        )
+    }
+
+    /// Verifies that `script_sig` unlocks `script_pubkey`
+    ///
+    /// The script sig is executed first and leaves its stack to the script pubkey,
+    /// which has to end with a true value on top of the stack. With `P2SH`, the
+    /// redeem script of a P2SH script pubkey, the last element the script sig
+    /// pushes, then runs on the rest of the stack the script sig left
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing why the verification failed
+    pub fn verify(&self, script_sig: &Script, script_pubkey: &Script) -> Result<()> {
+        if self.flags & VERIFY_SIGPUSHONLY != 0 && !script_sig.is_push_only() {
+            return Err(eyre!("Script sig is not push only"));
+        }
+
+        let mut stack = Stack::new();
+        self.eval(&mut stack, script_sig)?;
+        let mut p2sh_stack = stack.clone();
+        self.eval(&mut stack, script_pubkey)?;
+        ensure_true(&stack)?;
+
+        if self.flags & VERIFY_P2SH == 0 || !script_pubkey.is_p2sh() {
+            return Ok(());
+        }
+        if !script_sig.is_push_only() {
+            return Err(eyre!("Script sig spending P2SH is not push only"));
+        }
+
+        let redeem_script = p2sh_stack
+            .pop()
+            .ok_or_else(|| eyre!("No redeem script for P2SH"))?;
+        self.eval(&mut p2sh_stack, &Script::parse(&redeem_script)?)?;
+        ensure_true(&p2sh_stack)
+    }
+
+    /// Executes the script on the stack
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the script fails or breaks a limit
+    pub fn eval(&self, stack: &mut Stack, script: &Script) -> Result<()> {
+        if script.to_bytes().len() > MAX_SCRIPT_SIZE {
+            return Err(eyre!("Script larger than {MAX_SCRIPT_SIZE} bytes"));
+        }
+
+        let mut execution = Execution {
+            stack,
+            alt_stack: Stack::new(),
+            conditions: Vec::new(),
+            code_start: 0,
+            op_count: 0,
+            require_minimal: self.flags & VERIFY_MINIMALDATA != 0,
+        };
+
+        for (index, command) in script.commands.iter().enumerate() {
+            let executing = !execution.conditions.contains(&false);
+
+            match command {
+                Command::Push { data, .. } => {
+                    if data.len() > MAX_SCRIPT_ELEMENT_SIZE {
+                        return Err(eyre!("Push larger than {MAX_SCRIPT_ELEMENT_SIZE} bytes"));
+                    }
+                    if executing {
+                        if execution.require_minimal && !command.is_minimal_push() {
+                            return Err(eyre!("Push is not minimal"));
+                        }
+                        execution.stack.push(data.clone());
+                    }
+                }
+                Command::Unknown(byte) => {
+                    execution.op_count += 1;
+                    if executing {
+                        return Err(eyre!("Unknown opcode {byte:#04x}"));
+                    }
+                }
+                Command::Op(opcode) => {
+                    if opcode.to_byte() > Opcode::Op16.to_byte() {
+                        execution.op_count += 1;
+                    }
+                    if is_disabled(*opcode) {
+                        return Err(eyre!("Disabled opcode {opcode}"));
+                    }
+
+                    // Conditionals run in unexecuted branches to keep track of them
+                    let conditional = (Opcode::If.to_byte()..=Opcode::EndIf.to_byte())
+                        .contains(&opcode.to_byte());
+                    if executing || conditional {
+                        self.execute(*opcode, &mut execution, script, index)?;
+                    }
+                }
+            }
+
+            if execution.op_count > MAX_OPS_PER_SCRIPT {
+                return Err(eyre!("More than {MAX_OPS_PER_SCRIPT} opcodes"));
+            }
+            if execution.stack.len() + execution.alt_stack.len() > MAX_STACK_SIZE {
+                return Err(eyre!("More than {MAX_STACK_SIZE} stack elements"));
+            }
+        }
+
+        if !execution.conditions.is_empty() {
+            return Err(eyre!("OP_IF without OP_ENDIF"));
+        }
+        Ok(())
+    }
+
+    /// Executes an opcode, the command at `index` of `script`
+    fn execute(
+        &self,
+        opcode: Opcode,
+        execution: &mut Execution<'_>,
+        script: &Script,
+        index: usize,
+    ) -> Result<()> {
+        if let Some(value) = opcode.small_int() {
+            execution.stack.push(encode_num(i64::from(value)));
+            return Ok(());
+        }
+
+        match opcode {
+            Opcode::Op1Negate => execution.push_num(-1),
+            Opcode::Nop
+            | Opcode::Nop1
+            | Opcode::CheckLockTimeVerify
+            | Opcode::CheckSequenceVerify
+            | Opcode::Nop4
+            | Opcode::Nop5
+            | Opcode::Nop6
+            | Opcode::Nop7
+            | Opcode::Nop8
+            | Opcode::Nop9
+            | Opcode::Nop10 => {}
+
+            Opcode::If | Opcode::NotIf | Opcode::Else | Opcode::EndIf => {
+                execution.flow_control(opcode)?;
+            }
+            Opcode::Verify => {
+                if !execution.pop_bool()? {
+                    return Err(eyre!("OP_VERIFY failed"));
+                }
+            }
+            Opcode::Return => return Err(eyre!("OP_RETURN executed")),
+
+            Opcode::CodeSeparator => execution.code_start = index + 1,
+            Opcode::CheckSig | Opcode::CheckSigVerify => {
+                self.check_sig(execution, script, opcode == Opcode::CheckSigVerify)?;
+            }
+            Opcode::CheckMultiSig | Opcode::CheckMultiSigVerify => {
+                self.check_multisig(execution, script, opcode == Opcode::CheckMultiSigVerify)?;
+            }
+            Opcode::CheckSigAdd => return Err(eyre!("{opcode} is only valid in tapscript")),
+
+            Opcode::Ripemd160
+            | Opcode::Sha1
+            | Opcode::Sha256
+            | Opcode::Hash160
+            | Opcode::Hash256 => execution.hash(opcode)?,
+
+            _ if execution.stack_operation(opcode)? => {}
+            _ if execution.arithmetic(opcode)? => {}
+            _ => return Err(eyre!("Invalid opcode {opcode}")),
+        }
+
+        Ok(())
+    }
+
+    /// Executes `OP_CHECKSIG` and `OP_CHECKSIGVERIFY`
+    ///
+    /// The signature is checked against the legacy signature hash of the script code,
+    /// the script after the last `OP_CODESEPARATOR` without the signature itself
+    fn check_sig(
+        &self,
+        execution: &mut Execution<'_>,
+        script: &Script,
+        verify: bool,
+    ) -> Result<()> {
+        execution.require(2)?;
+        let public_key = execution.pop()?;
+        let signature = execution.pop()?;
+
+        let script_code = script_code(script, execution.code_start, &[&signature]);
+
+        self.check_signature_encoding(&signature)?;
+        self.check_public_key_encoding(&public_key)?;
+
+        let valid = self.check_signature(&signature, &public_key, &script_code);
+        if !valid && self.flags & VERIFY_NULLFAIL != 0 && !signature.is_empty() {
+            return Err(eyre!("Failed signature check with a non-empty signature"));
+        }
+
+        if verify {
+            if !valid {
+                return Err(eyre!("OP_CHECKSIGVERIFY failed"));
+            }
+        } else {
+            execution.push_bool(valid);
+        }
+        Ok(())
+    }
+
+    /// Executes `OP_CHECKMULTISIG` and `OP_CHECKMULTISIGVERIFY`
+    ///
+    /// Pops the number of public keys, the keys, the number of signatures, the
+    /// signatures and one more element, which the original implementation pops by
+    /// mistake. Signatures have to be in the order of their keys: each key is tried
+    /// once against the next signature, failing early once fewer keys than signatures
+    /// are left
+    fn check_multisig(
+        &self,
+        execution: &mut Execution<'_>,
+        script: &Script,
+        verify: bool,
+    ) -> Result<()> {
+        let key_count = usize::try_from(execution.pop_num()?)
+            .ok()
+            .filter(|&count| count <= MAX_PUBKEYS_PER_MULTISIG)
+            .ok_or_else(|| eyre!("Public key count out of range"))?;
+        execution.op_count += key_count;
+        if execution.op_count > MAX_OPS_PER_SCRIPT {
+            return Err(eyre!("More than {MAX_OPS_PER_SCRIPT} opcodes"));
+        }
+        execution.require(key_count)?;
+        let public_keys: Vec<Vec<u8>> = (0..key_count)
+            .map(|_| execution.pop())
+            .collect::<Result<_>>()?;
+
+        let signature_count = usize::try_from(execution.pop_num()?)
+            .ok()
+            .filter(|&count| count <= key_count)
+            .ok_or_else(|| eyre!("Signature count out of range"))?;
+        execution.require(signature_count)?;
+        let signatures: Vec<Vec<u8>> = (0..signature_count)
+            .map(|_| execution.pop())
+            .collect::<Result<_>>()?;
+
+        let signature_refs: Vec<&[u8]> = signatures.iter().map(Vec::as_slice).collect();
+        let script_code = script_code(script, execution.code_start, &signature_refs);
+
+        let (mut key_index, mut signature_index) = (0, 0);
+        let mut valid = true;
+        while valid && signature_index < signature_count {
+            let signature = &signatures[signature_index];
+            let public_key = &public_keys[key_index];
+            self.check_signature_encoding(signature)?;
+            self.check_public_key_encoding(public_key)?;
+
+            if self.check_signature(signature, public_key, &script_code) {
+                signature_index += 1;
+            }
+            key_index += 1;
+            valid = signature_count - signature_index <= key_count - key_index;
+        }
+
+        if !valid
+            && self.flags & VERIFY_NULLFAIL != 0
+            && signatures.iter().any(|signature| !signature.is_empty())
+        {
+            return Err(eyre!("Failed signature check with a non-empty signature"));
+        }
+
+        let dummy = execution.pop()?;
+        if self.flags & VERIFY_NULLDUMMY != 0 && !dummy.is_empty() {
+            return Err(eyre!("OP_CHECKMULTISIG dummy element is not empty"));
+        }
+
+        if verify {
+            if !valid {
+                return Err(eyre!("OP_CHECKMULTISIGVERIFY failed"));
+            }
+        } else {
+            execution.push_bool(valid);
+        }
+        Ok(())
+    }
+
+    /// Checks a signature, with its hash type as last byte, over the script code
+    ///
+    /// Signatures and public keys that can't be parsed are invalid, not errors. The
+    /// signature is parsed leniently like Bitcoin Core does, strict DER is enforced
+    /// by `check_signature_encoding` under `VERIFY_DERSIG`
+    fn check_signature(&self, signature: &[u8], public_key: &[u8], script_code: &Script) -> bool {
+        let Ok(public_key) = parse_public_key(public_key) else {
+            return false;
+        };
+        let Some((&hash_type, der)) = signature.split_last() else {
+            return false;
+        };
+        let Ok(signature) = Signature::parse_der_lax(der) else {
+            return false;
+        };
+
+        let sighash = self.tx.legacy_sighash(
+            self.input_index,
+            &script_code.to_bytes(),
+            u32::from(hash_type),
+        );
+        signature.verify(&sighash.z(), &public_key)
+    }
+
+    /// Applies the `DERSIG`, `LOW_S` and `STRICTENC` rules to a signature, empty signatures
+    /// are always allowed
+    fn check_signature_encoding(&self, signature: &[u8]) -> Result<()> {
+        let Some((&hash_type, der)) = signature.split_last() else {
+            return Ok(());
+        };
+
+        if self.flags & (VERIFY_DERSIG | VERIFY_LOW_S | VERIFY_STRICTENC) != 0
+            && !Signature::is_strict_der(der)
+        {
+            return Err(eyre!("Signature is not strict DER"));
+        }
+        if self.flags & VERIFY_LOW_S != 0 {
+            if let Ok(parsed) = Signature::parse_der(der) {
+                if !parsed.is_low_s() {
+                    return Err(eyre!("Signature has a high s"));
+                }
+            }
+        }
+        if self.flags & VERIFY_STRICTENC != 0 {
+            let base_type = u32::from(hash_type) & !SIGHASH_ANYONECANPAY;
+            if !(SIGHASH_ALL..=SIGHASH_SINGLE).contains(&base_type) {
+                return Err(eyre!("Undefined hash type {hash_type:#04x}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the STRICTENC rule to a public key, which has to be compressed or
+    /// uncompressed SEC
+    fn check_public_key_encoding(&self, public_key: &[u8]) -> Result<()> {
+        let valid = matches!(
+            (public_key.first(), public_key.len()),
+            (Some(4), 65) | (Some(2 | 3), 33)
+        );
+        if self.flags & VERIFY_STRICTENC != 0 && !valid {
+            return Err(eyre!("Public key is not SEC encoded"));
+        }
+        Ok(())
+    }
+}
+
+impl Execution<'_> {
+    fn require(&self, count: usize) -> Result<()> {
+        if self.stack.len() < count {
+            return Err(eyre!(
+                "{count} stack elements needed, {} available",
+                self.stack.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the element `depth` elements below the top of the stack
+    fn peek(&self, depth: usize) -> Result<Vec<u8>> {
+        self.require(depth + 1)?;
+        Ok(self.stack[self.stack.len() - 1 - depth].clone())
+    }
+
+    fn pop(&mut self) -> Result<Vec<u8>> {
+        self.stack.pop().ok_or_else(|| eyre!("Stack is empty"))
+    }
+
+    fn pop_num(&mut self) -> Result<i64> {
+        decode_num(&self.pop()?, self.require_minimal, MAX_NUM_SIZE)
+    }
+
+    fn pop_bool(&mut self) -> Result<bool> {
+        Ok(cast_to_bool(&self.pop()?))
+    }
+
+    fn push_num(&mut self, value: i64) {
+        self.stack.push(encode_num(value));
+    }
+
+    fn push_bool(&mut self, value: bool) {
+        self.push_num(i64::from(value));
+    }
+
+    /// Executes `OP_IF`, `OP_NOTIF`, `OP_ELSE` and `OP_ENDIF`, whether their branch
+    /// is executed or not
+    fn flow_control(&mut self, opcode: Opcode) -> Result<()> {
+        match opcode {
+            Opcode::If | Opcode::NotIf => {
+                let mut value = false;
+                if !self.conditions.contains(&false) {
+                    value = self
+                        .pop()
+                        .map_err(|_| eyre!("{opcode} without a condition"))
+                        .map(|top| cast_to_bool(&top))?;
+                    if opcode == Opcode::NotIf {
+                        value = !value;
+                    }
+                }
+                self.conditions.push(value);
+            }
+            Opcode::Else => {
+                let last = self
+                    .conditions
+                    .last_mut()
+                    .ok_or_else(|| eyre!("OP_ELSE without OP_IF"))?;
+                *last = !*last;
+            }
+            _ => {
+                self.conditions
+                    .pop()
+                    .ok_or_else(|| eyre!("OP_ENDIF without OP_IF"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Executes the stack opcodes, returns false for other opcodes
+    fn stack_operation(&mut self, opcode: Opcode) -> Result<bool> {
+        let length = self.stack.len();
+        match opcode {
+            Opcode::ToAltStack => {
+                let top = self.pop()?;
+                self.alt_stack.push(top);
+            }
+            Opcode::FromAltStack => {
+                let top = self
+                    .alt_stack
+                    .pop()
+                    .ok_or_else(|| eyre!("Alt stack is empty"))?;
+                self.stack.push(top);
+            }
+            Opcode::TwoDrop => {
+                self.require(2)?;
+                self.stack.truncate(length - 2);
+            }
+            Opcode::TwoDup | Opcode::ThreeDup | Opcode::TwoOver => {
+                let (depth, count) = match opcode {
+                    Opcode::TwoDup => (2, 2),
+                    Opcode::ThreeDup => (3, 3),
+                    _ => (4, 2),
+                };
+                self.require(depth)?;
+                let copied = self.stack[length - depth..length - depth + count].to_vec();
+                self.stack.extend(copied);
+            }
+            Opcode::TwoRot => {
+                self.require(6)?;
+                let moved: Vec<_> = self.stack.drain(length - 6..length - 4).collect();
+                self.stack.extend(moved);
+            }
+            Opcode::TwoSwap => {
+                self.require(4)?;
+                self.stack.swap(length - 4, length - 2);
+                self.stack.swap(length - 3, length - 1);
+            }
+            Opcode::IfDup => {
+                let top = self.peek(0)?;
+                if cast_to_bool(&top) {
+                    self.stack.push(top);
+                }
+            }
+            Opcode::Depth => self.push_num(i64::try_from(length)?),
+            Opcode::Drop => {
+                self.pop()?;
+            }
+            Opcode::Dup => self.stack.push(self.peek(0)?),
+            Opcode::Nip => {
+                self.require(2)?;
+                self.stack.remove(length - 2);
+            }
+            Opcode::Over => self.stack.push(self.peek(1)?),
+            Opcode::Pick | Opcode::Roll => {
+                let depth = self.pop_num()?;
+                let depth = usize::try_from(depth)
+                    .ok()
+                    .filter(|depth| *depth < self.stack.len())
+                    .ok_or_else(|| eyre!("{opcode} of {depth} out of the stack"))?;
+                let element = if opcode == Opcode::Roll {
+                    self.stack.remove(self.stack.len() - 1 - depth)
+                } else {
+                    self.peek(depth)?
+                };
+                self.stack.push(element);
+            }
+            Opcode::Rot => {
+                self.require(3)?;
+                let moved = self.stack.remove(length - 3);
+                self.stack.push(moved);
+            }
+            Opcode::Swap => {
+                self.require(2)?;
+                self.stack.swap(length - 2, length - 1);
+            }
+            Opcode::Tuck => {
+                let top = self.peek(0)?;
+                self.require(2)?;
+                self.stack.insert(length - 2, top);
+            }
+            Opcode::Size => {
+                let size = self.peek(0)?.len();
+                self.push_num(i64::try_from(size)?);
+            }
+            Opcode::Equal | Opcode::EqualVerify => {
+                self.require(2)?;
+                let equal = self.pop()? == self.pop()?;
+                if opcode == Opcode::Equal {
+                    self.push_bool(equal);
+                } else if !equal {
+                    return Err(eyre!("OP_EQUALVERIFY failed"));
+                }
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Executes the arithmetic opcodes, returns false for other opcodes
+    fn arithmetic(&mut self, opcode: Opcode) -> Result<bool> {
+        let unary: Option<fn(i64) -> i64> = match opcode {
+            Opcode::OneAdd => Some(|a| a + 1),
+            Opcode::OneSub => Some(|a| a - 1),
+            Opcode::Negate => Some(|a| -a),
+            Opcode::Abs => Some(i64::abs),
+            Opcode::Not => Some(|a| i64::from(a == 0)),
+            Opcode::ZeroNotEqual => Some(|a| i64::from(a != 0)),
+            _ => None,
+        };
+        if let Some(operation) = unary {
+            self.require(1)?;
+            let a = self.pop_num()?;
+            self.push_num(operation(a));
+            return Ok(true);
+        }
+
+        let binary: Option<fn(i64, i64) -> i64> = match opcode {
+            Opcode::Add => Some(|a, b| a + b),
+            Opcode::Sub => Some(|a, b| a - b),
+            Opcode::BoolAnd => Some(|a, b| i64::from(a != 0 && b != 0)),
+            Opcode::BoolOr => Some(|a, b| i64::from(a != 0 || b != 0)),
+            Opcode::NumEqual | Opcode::NumEqualVerify => Some(|a, b| i64::from(a == b)),
+            Opcode::NumNotEqual => Some(|a, b| i64::from(a != b)),
+            Opcode::LessThan => Some(|a, b| i64::from(a < b)),
+            Opcode::GreaterThan => Some(|a, b| i64::from(a > b)),
+            Opcode::LessThanOrEqual => Some(|a, b| i64::from(a <= b)),
+            Opcode::GreaterThanOrEqual => Some(|a, b| i64::from(a >= b)),
+            Opcode::Min => Some(i64::min),
+            Opcode::Max => Some(i64::max),
+            _ => None,
+        };
+        if let Some(operation) = binary {
+            self.require(2)?;
+            let b = self.pop_num()?;
+            let a = self.pop_num()?;
+            let result = operation(a, b);
+
+            if opcode == Opcode::NumEqualVerify {
+                if result == 0 {
+                    return Err(eyre!("OP_NUMEQUALVERIFY failed"));
+                }
+            } else {
+                self.push_num(result);
+            }
+            return Ok(true);
+        }
+
+        if opcode == Opcode::Within {
+            self.require(3)?;
+            let max = self.pop_num()?;
+            let min = self.pop_num()?;
+            let value = self.pop_num()?;
+            self.push_bool(min <= value && value < max);
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// Replaces the top of the stack by its hash
+    fn hash(&mut self, opcode: Opcode) -> Result<()> {
+        let data = self.pop()?;
+        let hash = match opcode {
+            Opcode::Ripemd160 => ripemd160(&data).to_vec(),
+            Opcode::Sha1 => sha1(&data).to_vec(),
+            Opcode::Sha256 => Sha256::digest(&data).to_vec(),
+            Opcode::Hash160 => hash160(&data).to_vec(),
+            _ => hash256(&data),
+        };
+        self.stack.push(hash);
+        Ok(())
+    }
+}
+
+/// Checks that the stack ends with a true value on top
+fn ensure_true(stack: &Stack) -> Result<()> {
+    match stack.last() {
+        Some(top) if cast_to_bool(top) => Ok(()),
+        _ => Err(eyre!("Script evaluated to false")),
+    }
+}
+
+/// Opcodes disabled since 2010, which fail the script even in an unexecuted branch
+fn is_disabled(opcode: Opcode) -> bool {
+    matches!(
+        opcode,
+        Opcode::Cat
+            | Opcode::Substr
+            | Opcode::Left
+            | Opcode::Right
+            | Opcode::Invert
+            | Opcode::And
+            | Opcode::Or
+            | Opcode::Xor
+            | Opcode::TwoMul
+            | Opcode::TwoDiv
+            | Opcode::Mul
+            | Opcode::Div
+            | Opcode::Mod
+            | Opcode::LShift
+            | Opcode::RShift
+    )
+}
+
+/// The script after the last executed `OP_CODESEPARATOR` without any push of the
+/// signatures, which can't sign themselves
+fn script_code(script: &Script, code_start: usize, signatures: &[&[u8]]) -> Script {
+    let signature_pushes: Vec<Command> = signatures
+        .iter()
+        .map(|signature| raw_push(signature.to_vec()))
+        .collect();
+
+    let mut script_code = Script::new(script.commands[code_start..].to_vec());
+    script_code
+        .commands
+        .retain(|command| !signature_pushes.contains(command));
+    script_code
+}
+
+/// The push of the data as Bitcoin Core's `CScript << data` writes it, with the
+/// smallest length prefix but never as `OP_1NEGATE` or `OP_1` to `OP_16`
+fn raw_push(data: Vec<u8>) -> Command {
+    match Command::push(data.clone()) {
+        push @ Command::Push { .. } => push,
+        _ => Command::Push {
+            kind: PushKind::Direct,
+            data,
+        },
+    }
+}
+
+/// Parses a public key like Bitcoin Core, which besides SEC accepts the hybrid
+/// encoding of X9.62, 0x06 for even y or 0x07 for odd y followed by both coordinates
+fn parse_public_key(bytes: &[u8]) -> Result<Secp256k1Point> {
+    let (Some(&prefix @ (6 | 7)), 65) = (bytes.first(), bytes.len()) else {
+        return Secp256k1Point::try_sec_parse(bytes);
+    };
+
+    let point = Secp256k1Point::try_sec_parse(&[&[4], &bytes[1..]].concat())?;
+    let odd = point.y().felt().is_some_and(|y| y.inner().bit(0));
+    if odd != (prefix == 7) {
+        return Err(eyre!("Hybrid public key with the wrong parity of y"));
+    }
+    Ok(point)
+}
@@ -0,0 +1,1012 @@
+#![allow(unused)]
+pub mod address;
+pub mod command;
+pub mod interpreter;
+pub mod number;
+pub mod opcode;
+#[allow(clippy::module_inception)]
+pub mod script;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        address::{p2sh_address, p2wsh_address, Network},
+        command::{Command, PushKind},
+        interpreter::{
+            Interpreter, MAX_OPS_PER_SCRIPT, MAX_SCRIPT_ELEMENT_SIZE, MAX_STACK_SIZE,
+            VERIFY_DERSIG, VERIFY_LOW_S, VERIFY_MINIMALDATA, VERIFY_NONE, VERIFY_NULLDUMMY,
+            VERIFY_NULLFAIL, VERIFY_P2SH, VERIFY_SIGPUSHONLY, VERIFY_STRICTENC,
+        },
+        number::{cast_to_bool, decode_num, encode_num, MAX_NUM_SIZE},
+        opcode::Opcode,
+        script::Script,
+    };
+    use crate::{
+        elliptic_curve::{
+            private_key::PrivateKey,
+            secp256k1::{Secp256k1Felt, Secp256k1Point},
+            signature::Signature,
+        },
+        encoding::encodable::{Decodable, Encodable},
+        helpers::bytes::{ToBytesBigEndian, ToHex},
+        transaction::{
+            input::{OutPoint, TxIn},
+            output::TxOut,
+            sighash::SIGHASH_ALL,
+            tx::Tx,
+        },
+    };
+    use color_eyre::eyre::Result;
+
+    /// P2PKH script pubkey of the transaction of chapter 5 of Programming Bitcoin
+    const P2PKH_HEX: &str = "76a914bc3b654dca7e56b04dca18f2566cdaf02e8d9ada88ac";
+
+    /// Script sig of the same transaction, a DER signature and a SEC public key
+    const SCRIPT_SIG_HEX: &str = "483045022100ed81ff192e75a3fd2304004dcadb746fa5e24c5031ccfcf21320b0277457c98f02207a986d955c6e0cb35d446a89d3f56100f4d7f67801c31967743a9c8e10615bed01210349fc4e631e3624a545de3f89f5d8684c7b8138bd94bdd531d2e213bf016b278a";
+
+    #[test]
+    fn test_opcodes() {
+        for byte in 0..=u8::MAX {
+            if let Some(opcode) = Opcode::from_byte(byte) {
+                assert_eq!(opcode.to_byte(), byte);
+                assert!(opcode.name().starts_with("OP_"));
+            }
+        }
+
+        // Pushes of 1 to 75 bytes and the unassigned bytes have no names
+        assert_eq!(Opcode::from_byte(0x01), None);
+        assert_eq!(Opcode::from_byte(0x4b), None);
+        assert_eq!(Opcode::from_byte(0xbb), None);
+        assert_eq!(Opcode::from_byte(0xfe), None);
+        assert_eq!(
+            (0..=u8::MAX).filter_map(Opcode::from_byte).count(),
+            256 - 75 - 68
+        );
+
+        assert_eq!(Opcode::CheckSig.to_byte(), 0xac);
+        assert_eq!(Opcode::CheckMultiSig.name(), "OP_CHECKMULTISIG");
+        assert_eq!(Opcode::CheckSigAdd.to_byte(), 0xba);
+
+        assert_eq!(Opcode::Op0.small_int(), Some(0));
+        assert_eq!(Opcode::Op16.small_int(), Some(16));
+        assert_eq!(Opcode::Op1Negate.small_int(), None);
+        for value in 0..=16 {
+            assert_eq!(
+                Opcode::from_small_int(value).unwrap().small_int(),
+                Some(value)
+            );
+        }
+        assert_eq!(Opcode::from_small_int(17), None);
+    }
+
+    #[test]
+    fn test_parse_script() {
+        let bytes = P2PKH_HEX.to_bytes_be();
+        let script = Script::parse(&bytes).unwrap();
+
+        assert_eq!(
+            script.commands,
+            vec![
+                Command::Op(Opcode::Dup),
+                Command::Op(Opcode::Hash160),
+                Command::Push {
+                    kind: PushKind::Direct,
+                    data: bytes[3..23].to_vec()
+                },
+                Command::Op(Opcode::EqualVerify),
+                Command::Op(Opcode::CheckSig),
+            ]
+        );
+        assert_eq!(script.to_bytes(), bytes);
+        assert_eq!(
+            script.to_string(),
+            "OP_DUP OP_HASH160 bc3b654dca7e56b04dca18f2566cdaf02e8d9ada OP_EQUALVERIFY OP_CHECKSIG"
+        );
+        assert!(!script.is_push_only());
+
+        let script_sig = Script::parse(&SCRIPT_SIG_HEX.to_bytes_be()).unwrap();
+        assert_eq!(script_sig.commands.len(), 2);
+        assert_eq!(script_sig.commands[0].data().unwrap().len(), 0x48);
+        assert_eq!(
+            script_sig.commands[1].data().unwrap().to_hex(),
+            "0349fc4e631e3624a545de3f89f5d8684c7b8138bd94bdd531d2e213bf016b278a"
+        );
+        assert!(script_sig.is_push_only());
+        assert!(script_sig.has_minimal_pushes());
+    }
+
+    #[test]
+    fn test_parse_pushdata() {
+        let mut bytes = vec![0x00, 0x4c, 0x02, 0xaa, 0xbb, 0x4d, 0x01, 0x00, 0xcc];
+        bytes.extend_from_slice(&[0x4e, 0x03, 0x00, 0x00, 0x00, 0x01, 0x02, 0x03]);
+        bytes.extend_from_slice(&[0x4f, 0x60, 0xbb, 0xff]);
+        let script = Script::parse(&bytes).unwrap();
+
+        assert_eq!(
+            script.commands,
+            vec![
+                Command::Push {
+                    kind: PushKind::Direct,
+                    data: vec![]
+                },
+                Command::Push {
+                    kind: PushKind::PushData1,
+                    data: vec![0xaa, 0xbb]
+                },
+                Command::Push {
+                    kind: PushKind::PushData2,
+                    data: vec![0xcc]
+                },
+                Command::Push {
+                    kind: PushKind::PushData4,
+                    data: vec![1, 2, 3]
+                },
+                Command::Op(Opcode::Op1Negate),
+                Command::Op(Opcode::Op16),
+                Command::Unknown(0xbb),
+                Command::Op(Opcode::InvalidOpcode),
+            ]
+        );
+        assert_eq!(script.to_bytes(), bytes);
+        assert_eq!(
+            script.to_string(),
+            "OP_0 aabb cc 010203 OP_1NEGATE OP_16 OP_UNKNOWN(0xbb) OP_INVALIDOPCODE"
+        );
+        assert!(!script.is_push_only());
+        assert!(!script.has_minimal_pushes());
+
+        // Length prefixed, as in transactions
+        let serialized = script.serialize();
+        assert_eq!(serialized[0] as usize, bytes.len());
+        assert_eq!(Script::deserialize(&serialized).unwrap(), script);
+    }
+
+    #[test]
+    fn test_parse_truncated_script() {
+        for bytes in [
+            &[0x01][..],
+            &[0x4b, 0x00],
+            &[0x4c],
+            &[0x4c, 0x02, 0x00],
+            &[0x4d, 0x01],
+            &[0x4d, 0x01, 0x00],
+            &[0x4e, 0x01, 0x00, 0x00],
+            &[0x4e, 0xff, 0xff, 0xff, 0xff, 0x00],
+            &[0x76, 0x02, 0x00],
+        ] {
+            assert!(Script::parse(bytes).is_err(), "{}", bytes.to_hex());
+        }
+
+        assert_eq!(Script::parse(&[]).unwrap(), Script::default());
+    }
+
+    #[test]
+    fn test_minimal_push() {
+        let push = |data: &[u8]| Command::push(data.to_vec());
+
+        assert_eq!(push(&[]).to_string(), "OP_0");
+        assert_eq!(push(&[]).serialize(), [0x00]);
+        assert_eq!(push(&[0x00]).serialize(), [0x01, 0x00]);
+        assert_eq!(push(&[0x01]), Command::Op(Opcode::Op1));
+        assert_eq!(push(&[0x10]), Command::Op(Opcode::Op16));
+        assert_eq!(push(&[0x11]).serialize(), [0x01, 0x11]);
+        assert_eq!(push(&[0x81]), Command::Op(Opcode::Op1Negate));
+        assert_eq!(push(&[0xaa; 75]).serialize()[0], 75);
+        assert_eq!(push(&[0xaa; 76]).serialize()[..2], [0x4c, 76]);
+        assert_eq!(push(&[0xaa; 255]).serialize()[..2], [0x4c, 0xff]);
+        assert_eq!(push(&[0xaa; 256]).serialize()[..3], [0x4d, 0x00, 0x01]);
+        assert_eq!(
+            push(&vec![0xaa; 0x10000]).serialize()[..5],
+            [0x4e, 0, 0, 1, 0]
+        );
+
+        for length in [0, 1, 75, 76, 255, 256, 520] {
+            assert!(push(&vec![0x11; length]).is_minimal_push());
+        }
+
+        let non_minimal = |kind, data: &[u8]| Command::Push {
+            kind,
+            data: data.to_vec(),
+        };
+        assert!(!non_minimal(PushKind::Direct, &[0x05]).is_minimal_push());
+        assert!(!non_minimal(PushKind::Direct, &[0x81]).is_minimal_push());
+        assert!(!non_minimal(PushKind::PushData1, &[]).is_minimal_push());
+        assert!(!non_minimal(PushKind::PushData1, &[0xaa; 75]).is_minimal_push());
+        assert!(!non_minimal(PushKind::PushData2, &[0xaa; 255]).is_minimal_push());
+        assert!(!non_minimal(PushKind::PushData4, &[0xaa; 256]).is_minimal_push());
+        assert!(Command::Op(Opcode::Op5).is_minimal_push());
+
+        // Too long to be pushed directly
+        let mut bytes = Vec::new();
+        assert!(non_minimal(PushKind::Direct, &[0xaa; 76])
+            .encode(&mut bytes)
+            .is_err());
+    }
+
+    #[test]
+    fn test_build_script() {
+        let key_hash = [0x11; 20];
+        let script = Script::new(vec![
+            Command::Op(Opcode::Dup),
+            Command::Op(Opcode::Hash160),
+            Command::push(key_hash.to_vec()),
+            Command::Op(Opcode::EqualVerify),
+            Command::Op(Opcode::CheckSig),
+        ]);
+
+        let bytes = script.to_bytes();
+        assert_eq!(bytes.len(), 25);
+        assert_eq!(&bytes[..3], &[0x76, 0xa9, 0x14]);
+        assert_eq!(Script::parse(&bytes).unwrap(), script);
+    }
+
+    /// Parses a script written like in Bitcoin Core's `script_tests.json`: numbers,
+    /// opcode names with or without `OP_`, quoted strings, and `0x` hex inserted as is
+    fn parse_asm(asm: &str) -> Script {
+        Script::parse(&asm_to_bytes(asm)).unwrap()
+    }
+
+    /// Assembles a script in the format of Bitcoin Core's tests, which may not parse
+    fn asm_to_bytes(asm: &str) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for token in asm.split_whitespace() {
+            if let Ok(number) = token.parse::<i64>() {
+                Command::push(encode_num(number))
+                    .encode(&mut bytes)
+                    .unwrap();
+            } else if let Some(hex) = token.strip_prefix("0x") {
+                bytes.extend(hex.to_bytes_be());
+            } else if let Some(string) = token
+                .strip_prefix('\'')
+                .and_then(|token| token.strip_suffix('\''))
+            {
+                Command::push(string.as_bytes().to_vec())
+                    .encode(&mut bytes)
+                    .unwrap();
+            } else {
+                let name = format!("OP_{}", token.trim_start_matches("OP_"));
+                let opcode = (0..=u8::MAX)
+                    .filter_map(Opcode::from_byte)
+                    .find(|opcode| opcode.name() == name)
+                    .unwrap_or_else(|| panic!("Unknown opcode {token}"));
+                bytes.push(opcode.to_byte());
+            }
+        }
+        bytes
+    }
+
+    /// Builds the transaction spending the output of a transaction locked by
+    /// `script_pubkey`, like Bitcoin Core's script tests do
+    fn spending_tx(script_pubkey: &Script, script_sig: &Script) -> Tx {
+        let credit = Tx::new(
+            1,
+            vec![TxIn::new(OutPoint::null(), vec![0x00, 0x00], u32::MAX)],
+            vec![TxOut::new(0, script_pubkey.to_bytes())],
+            0,
+        );
+        Tx::new(
+            1,
+            vec![TxIn::new(
+                OutPoint::new(credit.txid(), 0),
+                script_sig.to_bytes(),
+                u32::MAX,
+            )],
+            vec![TxOut::new(0, Vec::new())],
+            0,
+        )
+    }
+
+    fn verify(script_sig: &Script, script_pubkey: &Script, flags: u32) -> Result<()> {
+        let tx = spending_tx(script_pubkey, script_sig);
+        Interpreter::new(&tx, 0, flags).verify(script_sig, script_pubkey)
+    }
+
+    /// Signs the input of the spending transaction over the script code, with the
+    /// hash type appended to the DER signature
+    fn sign(
+        key: &PrivateKey,
+        script_pubkey: &Script,
+        script_code: &Script,
+        hash_type: u8,
+    ) -> Vec<u8> {
+        let tx = spending_tx(script_pubkey, &Script::default());
+        let sighash = tx.legacy_sighash(0, &script_code.to_bytes(), u32::from(hash_type));
+        let mut signature = key.sign(&sighash.z()).der();
+        signature.push(hash_type);
+        signature
+    }
+
+    fn public_key(key: &PrivateKey) -> Vec<u8> {
+        let public_key = key.public_key().sec_compressed().unwrap();
+        assert_eq!(public_key.len(), 33);
+        public_key
+    }
+
+    #[test]
+    fn test_script_numbers() {
+        let cases: [(i64, &str); 11] = [
+            (0, ""),
+            (1, "01"),
+            (-1, "81"),
+            (127, "7f"),
+            (128, "8000"),
+            (-128, "8080"),
+            (255, "ff00"),
+            (256, "0001"),
+            (-255, "ff80"),
+            (0x7fff_ffff, "ffffff7f"),
+            (-0x7fff_ffff, "ffffffff"),
+        ];
+        for (value, hex) in cases {
+            assert_eq!(encode_num(value).to_hex(), hex);
+            assert_eq!(
+                decode_num(&encode_num(value), true, MAX_NUM_SIZE).unwrap(),
+                value
+            );
+        }
+        for value in -1000..1000 {
+            assert_eq!(
+                decode_num(&encode_num(value), true, MAX_NUM_SIZE).unwrap(),
+                value
+            );
+        }
+
+        assert!(decode_num(&encode_num(0x8000_0000), false, MAX_NUM_SIZE).is_err());
+        assert_eq!(
+            decode_num(&encode_num(0x8000_0000), false, 5).unwrap(),
+            0x8000_0000
+        );
+
+        // Trailing bytes only holding a sign are not minimal
+        assert_eq!(decode_num(&[0x05, 0x00], false, MAX_NUM_SIZE).unwrap(), 5);
+        assert!(decode_num(&[0x05, 0x00], true, MAX_NUM_SIZE).is_err());
+        assert_eq!(decode_num(&[0x05, 0x80], false, MAX_NUM_SIZE).unwrap(), -5);
+        assert!(decode_num(&[0x05, 0x80], true, MAX_NUM_SIZE).is_err());
+        assert_eq!(decode_num(&[0x80], false, MAX_NUM_SIZE).unwrap(), 0);
+        assert!(decode_num(&[0x80], true, MAX_NUM_SIZE).is_err());
+        assert!(decode_num(&[0x00], true, MAX_NUM_SIZE).is_err());
+
+        assert!(!cast_to_bool(&[]));
+        assert!(!cast_to_bool(&[0x00, 0x00]));
+        assert!(!cast_to_bool(&[0x00, 0x80]));
+        assert!(cast_to_bool(&[0x80, 0x00]));
+        assert!(cast_to_bool(&[0x00, 0x01]));
+    }
+
+    #[test]
+    fn test_interpreter() {
+        // (script sig, script pubkey, flags, valid), in the format of Bitcoin Core's
+        // script_tests.json
+        let cases = [
+            ("", "", VERIFY_NONE, false),
+            ("0", "", VERIFY_NONE, false),
+            ("1", "", VERIFY_NONE, true),
+            ("0x01 0x0b", "11 EQUAL", VERIFY_NONE, true),
+            ("1 2", "2 EQUALVERIFY 1 EQUAL", VERIFY_NONE, true),
+            ("'abc'", "SIZE 3 EQUALVERIFY 'abc' EQUAL", VERIFY_NONE, true),
+            ("0x01 0x80", "IF 0 ELSE 1 ENDIF", VERIFY_NONE, true),
+            // Flow control
+            ("1", "IF 1 ELSE 0 ENDIF", VERIFY_NONE, true),
+            ("0", "IF 0 ELSE 1 ENDIF", VERIFY_NONE, true),
+            ("0", "NOTIF 1 ELSE 0 ENDIF", VERIFY_NONE, true),
+            ("1 1", "IF IF 1 ELSE 0 ENDIF ENDIF", VERIFY_NONE, true),
+            ("0", "IF 0 ELSE 1 ELSE 0 ENDIF", VERIFY_NONE, true),
+            ("1", "IF 0 ELSE 1 ELSE 0 ENDIF", VERIFY_NONE, false),
+            ("1", "IF 1", VERIFY_NONE, false),
+            ("1", "ELSE 1 ENDIF", VERIFY_NONE, false),
+            ("1", "ENDIF", VERIFY_NONE, false),
+            ("1 IF", "1 ENDIF", VERIFY_NONE, false),
+            ("0", "IF RETURN ENDIF 1", VERIFY_NONE, true),
+            ("1", "IF RETURN ENDIF 1", VERIFY_NONE, false),
+            ("1", "VERIFY 1", VERIFY_NONE, true),
+            ("0", "VERIFY 1", VERIFY_NONE, false),
+            // Reserved, unknown and disabled opcodes
+            ("0", "IF VER ENDIF 1", VERIFY_NONE, true),
+            ("1", "IF VER ENDIF 1", VERIFY_NONE, false),
+            ("0", "IF VERIF ENDIF 1", VERIFY_NONE, false),
+            ("0", "IF RESERVED ENDIF 1", VERIFY_NONE, true),
+            ("1", "RESERVED", VERIFY_NONE, false),
+            ("0", "IF 0xbb ENDIF 1", VERIFY_NONE, true),
+            ("1", "IF 0xbb ENDIF 1", VERIFY_NONE, false),
+            ("0", "IF CAT ENDIF 1", VERIFY_NONE, false),
+            ("2 3", "MUL 6 EQUAL", VERIFY_NONE, false),
+            ("1", "NOP NOP1 CHECKLOCKTIMEVERIFY CHECKSEQUENCEVERIFY NOP10", VERIFY_NONE, true),
+            // Stack
+            ("1", "TOALTSTACK DEPTH 0 EQUALVERIFY FROMALTSTACK", VERIFY_NONE, true),
+            ("1", "FROMALTSTACK", VERIFY_NONE, false),
+            ("1 TOALTSTACK", "FROMALTSTACK", VERIFY_NONE, false),
+            ("0", "IFDUP DEPTH 1 EQUAL", VERIFY_NONE, true),
+            ("1", "IFDUP DEPTH 2 EQUAL", VERIFY_NONE, true),
+            ("1 2", "2DUP DEPTH 4 EQUALVERIFY 2 EQUALVERIFY 1 EQUALVERIFY 2 EQUAL", VERIFY_NONE, true),
+            ("1 2 3", "3DUP DEPTH 6 EQUAL", VERIFY_NONE, true),
+            ("1 2 3 4", "2OVER 2 EQUALVERIFY 1 EQUALVERIFY 2DROP 2DROP DEPTH 0 EQUAL", VERIFY_NONE, true),
+            ("1 2 3 4 5 6", "2ROT 2 EQUALVERIFY 1 EQUALVERIFY 6 EQUALVERIFY 5 EQUALVERIFY 4 EQUALVERIFY 3 EQUAL", VERIFY_NONE, true),
+            ("1 2 3 4", "2SWAP 2 EQUALVERIFY 1 EQUALVERIFY 4 EQUALVERIFY 3 EQUAL", VERIFY_NONE, true),
+            ("1 2", "NIP 2 EQUALVERIFY DEPTH 0 EQUAL", VERIFY_NONE, true),
+            ("1 2", "OVER 1 EQUALVERIFY DEPTH 2 EQUAL", VERIFY_NONE, true),
+            ("1 2 3 4", "2 PICK 2 EQUALVERIFY DEPTH 4 EQUAL", VERIFY_NONE, true),
+            ("1 2 3 4", "3 ROLL 1 EQUALVERIFY DEPTH 3 EQUAL", VERIFY_NONE, true),
+            ("1", "2 PICK", VERIFY_NONE, false),
+            ("1", "-1 PICK", VERIFY_NONE, false),
+            ("1 2 3", "ROT 1 EQUALVERIFY 3 EQUALVERIFY 2 EQUAL", VERIFY_NONE, true),
+            ("1 2", "SWAP 1 EQUALVERIFY 2 EQUAL", VERIFY_NONE, true),
+            ("1 2", "TUCK DEPTH 3 EQUALVERIFY SWAP 2DROP", VERIFY_NONE, true),
+            ("1", "DUP DROP DROP", VERIFY_NONE, false),
+            // Arithmetic
+            ("2 3", "ADD 5 EQUAL", VERIFY_NONE, true),
+            ("2 3", "SUB -1 EQUAL", VERIFY_NONE, true),
+            ("-5", "ABS 5 EQUAL", VERIFY_NONE, true),
+            ("5", "NEGATE -5 EQUAL", VERIFY_NONE, true),
+            ("5", "1ADD 1SUB 5 NUMEQUAL", VERIFY_NONE, true),
+            ("0", "NOT", VERIFY_NONE, true),
+            ("2", "0NOTEQUAL", VERIFY_NONE, true),
+            ("1 0", "BOOLOR", VERIFY_NONE, true),
+            ("1 0", "BOOLAND NOT", VERIFY_NONE, true),
+            ("3 4", "LESSTHAN", VERIFY_NONE, true),
+            ("4 3", "GREATERTHAN", VERIFY_NONE, true),
+            ("3 3", "LESSTHANOREQUAL", VERIFY_NONE, true),
+            ("3 3", "GREATERTHANOREQUAL", VERIFY_NONE, true),
+            ("3 4", "NUMNOTEQUAL", VERIFY_NONE, true),
+            ("3 2", "MIN 2 EQUAL", VERIFY_NONE, true),
+            ("3 2", "MAX 3 EQUAL", VERIFY_NONE, true),
+            ("2 1 3", "WITHIN", VERIFY_NONE, true),
+            ("3 1 3", "WITHIN NOT", VERIFY_NONE, true),
+            ("3 3", "NUMEQUALVERIFY 1", VERIFY_NONE, true),
+            ("3 4", "NUMEQUALVERIFY 1", VERIFY_NONE, false),
+            ("2147483647", "1ADD 2147483648 EQUAL", VERIFY_NONE, true),
+            ("2147483648", "1ADD", VERIFY_NONE, false),
+            ("0x01 0x80", "NOT", VERIFY_NONE, true),
+            ("0x01 0x80", "NOT", VERIFY_MINIMALDATA, false),
+            // Hashes
+            ("''", "SHA256 0x20 0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855 EQUAL", VERIFY_NONE, true),
+            ("''", "HASH256 0x20 0x5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456 EQUAL", VERIFY_NONE, true),
+            ("''", "HASH160 0x14 0xb472a266d0bd89c13706a4132ccfb16f7c3b9fcb EQUAL", VERIFY_NONE, true),
+            ("'a'", "RIPEMD160 0x14 0x0bdc9d2d256b3ee9daae347be6f4dc835a467ffe EQUAL", VERIFY_NONE, true),
+            ("'a'", "SHA1 0x14 0x86f7e437faa5a7fce15d1ddcb9eaeaea377667b8 EQUAL", VERIFY_NONE, true),
+            // Flags
+            ("0x01 0x05", "5 EQUAL", VERIFY_NONE, true),
+            ("0x01 0x05", "5 EQUAL", VERIFY_MINIMALDATA, false),
+            ("0x4c 0x01 0x07", "7 EQUAL", VERIFY_MINIMALDATA, false),
+            ("0", "IF 0x4c 0x01 0x07 ENDIF 1", VERIFY_MINIMALDATA, true),
+            ("1 NOP", "1", VERIFY_NONE, true),
+            ("1 NOP", "1", VERIFY_SIGPUSHONLY, false),
+        ];
+
+        for (script_sig, script_pubkey, flags, valid) in cases {
+            let result = verify(&parse_asm(script_sig), &parse_asm(script_pubkey), flags);
+            assert_eq!(
+                result.is_ok(),
+                valid,
+                "[{script_sig:?}, {script_pubkey:?}]: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_script_core_vectors() {
+        // Bitcoin Core's script_tests.json, [script sig, script pubkey, flags, expected
+        // result, comment]. Segwit entries start with the witness and are skipped
+        let data: serde_json::Value = serde_json::from_str(include_str!(
+            "../../test_data/bitcoin_core/script_tests.json"
+        ))
+        .unwrap();
+
+        let mut count = 0;
+        for entry in data.as_array().unwrap() {
+            if entry.as_array().unwrap().len() < 4 || entry[0].is_array() {
+                continue;
+            }
+            let Some(flags) = script_test_flags(entry[2].as_str().unwrap()) else {
+                continue;
+            };
+            let expected = entry[3] == "OK";
+
+            // Core fails scripts with truncated pushes when executing them
+            let (Ok(script_sig), Ok(script_pubkey)) = (
+                Script::parse(&asm_to_bytes(entry[0].as_str().unwrap())),
+                Script::parse(&asm_to_bytes(entry[1].as_str().unwrap())),
+            ) else {
+                assert!(!expected, "{entry}");
+                continue;
+            };
+
+            let result = verify(&script_sig, &script_pubkey, flags);
+            assert_eq!(result.is_ok(), expected, "{entry} {result:?}");
+            count += 1;
+        }
+
+        // Most of the entries are run
+        assert!(count > 900, "{count}");
+    }
+
+    /// Parses the flags of a `script_tests.json` entry, `None` for entries needing
+    /// rules the interpreter doesn't implement
+    fn script_test_flags(names: &str) -> Option<u32> {
+        let mut flags = VERIFY_NONE;
+        for name in names.split(',').filter(|name| !name.is_empty()) {
+            flags |= match name {
+                "NONE" => VERIFY_NONE,
+                "P2SH" => VERIFY_P2SH,
+                "STRICTENC" => VERIFY_STRICTENC,
+                "DERSIG" => VERIFY_DERSIG,
+                "LOW_S" => VERIFY_LOW_S,
+                "NULLDUMMY" => VERIFY_NULLDUMMY,
+                "SIGPUSHONLY" => VERIFY_SIGPUSHONLY,
+                "MINIMALDATA" => VERIFY_MINIMALDATA,
+                "NULLFAIL" => VERIFY_NULLFAIL,
+                // Segwit, taproot, lock times, the clean stack rule and the policy
+                // rules discouraging upgradable opcodes
+                "WITNESS"
+                | "WITNESS_PUBKEYTYPE"
+                | "MINIMALIF"
+                | "TAPROOT"
+                | "DISCOURAGE_UPGRADABLE_WITNESS_PROGRAM"
+                | "CHECKLOCKTIMEVERIFY"
+                | "CHECKSEQUENCEVERIFY"
+                | "CLEANSTACK"
+                | "DISCOURAGE_UPGRADABLE_NOPS" => return None,
+                _ => panic!("Unknown flag {name}"),
+            };
+        }
+        Some(flags)
+    }
+
+    #[test]
+    fn test_interpreter_limits() {
+        let script_sig = parse_asm("1");
+        let nops = |count| Script::new(vec![Command::Op(Opcode::Nop); count]);
+        assert!(verify(&script_sig, &nops(MAX_OPS_PER_SCRIPT), VERIFY_NONE).is_ok());
+        assert!(verify(&script_sig, &nops(MAX_OPS_PER_SCRIPT + 1), VERIFY_NONE).is_err());
+
+        let push = |size| {
+            Script::new(vec![
+                Command::push(vec![0x01; size]),
+                Command::Op(Opcode::Size),
+                Command::Op(Opcode::Drop),
+            ])
+        };
+        assert!(verify(
+            &Script::default(),
+            &push(MAX_SCRIPT_ELEMENT_SIZE),
+            VERIFY_NONE
+        )
+        .is_ok());
+        assert!(verify(
+            &Script::default(),
+            &push(MAX_SCRIPT_ELEMENT_SIZE + 1),
+            VERIFY_NONE
+        )
+        .is_err());
+
+        let pushes = |count| Script::new(vec![Command::push(vec![0x01]); count]);
+        assert!(verify(&pushes(MAX_STACK_SIZE), &Script::default(), VERIFY_NONE).is_ok());
+        assert!(verify(&pushes(MAX_STACK_SIZE + 1), &Script::default(), VERIFY_NONE).is_err());
+
+        // 19 and 20 pushes of 500 bytes around the 10000 bytes a script can have
+        let large = |count| {
+            let mut commands = Vec::new();
+            for _ in 0..count {
+                commands.push(Command::push(vec![0x01; 500]));
+                commands.push(Command::Op(Opcode::Drop));
+            }
+            commands.push(Command::Op(Opcode::Op1));
+            Script::new(commands)
+        };
+        assert!(verify(&Script::default(), &large(19), VERIFY_NONE).is_ok());
+        assert!(verify(&Script::default(), &large(20), VERIFY_NONE).is_err());
+    }
+
+    #[test]
+    fn test_verify_p2pkh() {
+        let tx_hex = "0100000001813f79011acb80925dfe69b3def355fe914bd1d96a3f5f71bf8303c6a989c7d1000000006b483045022100ed81ff192e75a3fd2304004dcadb746fa5e24c5031ccfcf21320b0277457c98f02207a986d955c6e0cb35d446a89d3f56100f4d7f67801c31967743a9c8e10615bed01210349fc4e631e3624a545de3f89f5d8684c7b8138bd94bdd531d2e213bf016b278afeffffff02a135ef01000000001976a914bc3b654dca7e56b04dca18f2566cdaf02e8d9ada88ac99c39800000000001976a9141c4bc762dd5423e332166702cb75f40df79fea1288ac19430600";
+        let script_pubkey = "76a914a802fc56c704ce87c42d7c92eb75e7896bdc41ae88ac".to_bytes_be();
+        let flags = VERIFY_STRICTENC | VERIFY_DERSIG | VERIFY_LOW_S | VERIFY_NULLFAIL;
+
+        let mut tx = Tx::deserialize(&tx_hex.to_bytes_be()).unwrap();
+        assert!(Interpreter::new(&tx, 0, flags)
+            .verify_input(&script_pubkey)
+            .is_ok());
+        assert!(Interpreter::new(&tx, 1, flags)
+            .verify_input(&script_pubkey)
+            .is_err());
+
+        // Paying to another key hash
+        let mut other_script_pubkey = script_pubkey.clone();
+        other_script_pubkey[3] ^= 1;
+        assert!(Interpreter::new(&tx, 0, flags)
+            .verify_input(&other_script_pubkey)
+            .is_err());
+
+        // The signature no longer commits to the transaction
+        tx.outputs[0].value += 1;
+        assert!(Interpreter::new(&tx, 0, VERIFY_NONE)
+            .verify_input(&script_pubkey)
+            .is_err());
+    }
+
+    #[test]
+    fn test_checksig() {
+        let key = PrivateKey::new(Secp256k1Felt::new(12345u32.into()));
+        let other_key = PrivateKey::new(Secp256k1Felt::new(54321u32.into()));
+        let p2pk = |key: &PrivateKey| {
+            Script::new(vec![
+                Command::push(public_key(key)),
+                Command::Op(Opcode::CheckSig),
+            ])
+        };
+        let all_flags = VERIFY_STRICTENC | VERIFY_DERSIG | VERIFY_LOW_S | VERIFY_NULLFAIL;
+
+        let script_pubkey = p2pk(&key);
+        let signature = sign(&key, &script_pubkey, &script_pubkey, 0x01);
+        let script_sig = Script::new(vec![Command::push(signature.clone())]);
+        assert!(verify(&script_sig, &script_pubkey, all_flags).is_ok());
+        assert!(verify(&script_sig, &p2pk(&other_key), VERIFY_NONE).is_err());
+
+        // OP_CHECKSIGVERIFY leaves nothing on the stack
+        let mut verify_script = p2pk(&key);
+        verify_script.commands[1] = Command::Op(Opcode::CheckSigVerify);
+        let signature = sign(&key, &verify_script, &verify_script, 0x01);
+        let mut script_sig = Script::new(vec![Command::push(signature)]);
+        assert!(verify(&script_sig, &verify_script, all_flags).is_err());
+        script_sig.commands.insert(0, Command::push(vec![0x01]));
+        assert!(verify(&script_sig, &verify_script, all_flags).is_ok());
+
+        // A failed check with NOT, the signature has to be empty with NULLFAIL
+        let not_script = parse_asm(&format!(
+            "0x21 0x{} CHECKSIG NOT",
+            public_key(&key).to_hex()
+        ));
+        let wrong_signature = sign(&other_key, &not_script, &not_script, 0x01);
+        let script_sig = Script::new(vec![Command::push(wrong_signature)]);
+        assert!(verify(&script_sig, &not_script, VERIFY_NONE).is_ok());
+        assert!(verify(&script_sig, &not_script, VERIFY_NULLFAIL).is_err());
+        assert!(verify(&parse_asm("0"), &not_script, VERIFY_NULLFAIL).is_ok());
+
+        // High s, flipped from the low s signature
+        let script_pubkey = p2pk(&key);
+        let tx = spending_tx(&script_pubkey, &Script::default());
+        let z = tx
+            .legacy_sighash(0, &script_pubkey.to_bytes(), SIGHASH_ALL)
+            .z();
+        let low_s = key.sign(&z);
+        let high_s = Signature::new(
+            low_s.r().clone(),
+            Secp256k1Felt::new(Secp256k1Point::order() - low_s.s().inner()),
+        );
+        let mut signature = high_s.der();
+        signature.push(0x01);
+        let script_sig = Script::new(vec![Command::push(signature)]);
+        assert!(verify(&script_sig, &script_pubkey, VERIFY_STRICTENC).is_ok());
+        assert!(verify(&script_sig, &script_pubkey, VERIFY_LOW_S).is_err());
+
+        // Undefined hash type
+        let signature = sign(&key, &script_pubkey, &script_pubkey, 0x21);
+        let script_sig = Script::new(vec![Command::push(signature)]);
+        assert!(verify(&script_sig, &script_pubkey, VERIFY_NONE).is_ok());
+        assert!(verify(&script_sig, &script_pubkey, VERIFY_STRICTENC).is_err());
+
+        // r padded with a needless zero byte isn't DER, but is valid without DERSIG
+        let signature = sign(&key, &script_pubkey, &script_pubkey, 0x01);
+        let r_length = usize::from(signature[3]);
+        let mut padded = vec![0x30, signature[1] + 1, 0x02, signature[3] + 1, 0x00];
+        padded.extend(&signature[4..]);
+        assert_eq!(padded.len(), signature.len() + 1);
+        assert_eq!(padded[5..5 + r_length], signature[4..4 + r_length]);
+        let script_sig = Script::new(vec![Command::push(padded)]);
+        assert!(verify(&script_sig, &script_pubkey, VERIFY_NONE).is_ok());
+        assert!(verify(&script_sig, &script_pubkey, VERIFY_DERSIG).is_err());
+
+        // Hybrid public keys, with the parity of y in the prefix, are valid but not
+        // under STRICTENC
+        let mut hybrid = key.public_key().sec_uncompressed().unwrap();
+        hybrid[0] = 0x06 | (hybrid[64] & 1);
+        let hybrid_script = Script::new(vec![
+            Command::push(hybrid.clone()),
+            Command::Op(Opcode::CheckSig),
+        ]);
+        let signature = sign(&key, &hybrid_script, &hybrid_script, 0x01);
+        let script_sig = Script::new(vec![Command::push(signature)]);
+        assert!(verify(&script_sig, &hybrid_script, VERIFY_NONE).is_ok());
+        assert!(verify(&script_sig, &hybrid_script, VERIFY_STRICTENC).is_err());
+
+        // The wrong parity makes the public key invalid
+        hybrid[0] ^= 1;
+        let wrong_parity = Script::new(vec![
+            Command::push(hybrid),
+            Command::Op(Opcode::CheckSig),
+            Command::Op(Opcode::Not),
+        ]);
+        let signature = sign(&key, &wrong_parity, &wrong_parity, 0x01);
+        let script_sig = Script::new(vec![Command::push(signature)]);
+        assert!(verify(&script_sig, &wrong_parity, VERIFY_NONE).is_ok());
+    }
+
+    #[test]
+    fn test_checksig_script_code() {
+        let key = PrivateKey::new(Secp256k1Felt::new(12345u32.into()));
+        let public_key = public_key(&key).to_hex();
+
+        // Only the script after the last executed OP_CODESEPARATOR is signed
+        let script_pubkey = parse_asm(&format!(
+            "'junk' DROP CODESEPARATOR 0x21 0x{public_key} CHECKSIG"
+        ));
+        let script_code = parse_asm(&format!("0x21 0x{public_key} CHECKSIG"));
+        let signature = sign(&key, &script_pubkey, &script_code, 0x01);
+        let script_sig = Script::new(vec![Command::push(signature)]);
+        assert!(verify(&script_sig, &script_pubkey, VERIFY_NONE).is_ok());
+
+        let signature = sign(&key, &script_pubkey, &script_pubkey, 0x01);
+        let script_sig = Script::new(vec![Command::push(signature)]);
+        assert!(verify(&script_sig, &script_pubkey, VERIFY_NONE).is_err());
+
+        // The signature is removed from the script code, so it can sign a script
+        // containing itself. The transaction is the one signed, as the crediting
+        // transaction would depend on the signature
+        let script_code = parse_asm(&format!("DROP 0x21 0x{public_key} CHECKSIG"));
+        let signature = sign(&key, &script_code, &script_code, 0x01);
+        let mut script_pubkey = script_code.clone();
+        script_pubkey
+            .commands
+            .insert(0, Command::push(signature.clone()));
+        let script_sig = Script::new(vec![Command::push(signature)]);
+        let tx = spending_tx(&script_code, &script_sig);
+        assert!(Interpreter::new(&tx, 0, VERIFY_NONE)
+            .verify(&script_sig, &script_pubkey)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_multisig_script() {
+        // Vectors of BIP 67
+        let parse_keys = |keys: &[&str]| -> Vec<Secp256k1Point> {
+            keys.iter()
+                .map(|key| Secp256k1Point::try_sec_parse(&key.to_bytes_be()).unwrap())
+                .collect()
+        };
+        let keys = parse_keys(&[
+            "02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8",
+            "02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f",
+        ]);
+        let redeem_script = Script::sorted_multisig(2, &keys).unwrap();
+        assert_eq!(
+            redeem_script.to_bytes().to_hex(),
+            "522102fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f2102ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f852ae"
+        );
+        assert_eq!(
+            p2sh_address(&redeem_script, Network::Mainnet).unwrap(),
+            "39bgKC7RFbpoCRbtD5KEdkYKtNyhpsNa3Z"
+        );
+
+        // Unsorted, the keys keep their order
+        let unsorted = Script::multisig(2, &keys).unwrap();
+        assert_eq!(
+            unsorted.commands[1].data(),
+            keys[0].sec_compressed().as_deref()
+        );
+        assert_ne!(unsorted, redeem_script);
+
+        let keys = parse_keys(&[
+            "02e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b404",
+            "027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e77",
+            "02632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed0",
+        ]);
+        let redeem_script = Script::sorted_multisig(2, &keys).unwrap();
+        assert_eq!(
+            p2sh_address(&redeem_script, Network::Mainnet).unwrap(),
+            "3CKHTjBKxCARLzwABMu9yD85kvtm7WnMfH"
+        );
+        assert!(p2sh_address(&redeem_script, Network::Testnet)
+            .unwrap()
+            .starts_with('2'));
+
+        let p2sh = Script::p2sh(&redeem_script);
+        assert!(p2sh.is_p2sh());
+        assert_eq!(p2sh.to_bytes().len(), 23);
+        assert!(!redeem_script.is_p2sh());
+        assert_eq!(Script::p2wsh(&redeem_script).to_bytes()[..2], [0x00, 0x20]);
+
+        assert!(Script::multisig(0, &keys).is_err());
+        assert!(Script::multisig(4, &keys).is_err());
+        assert!(Script::multisig(1, &[]).is_err());
+        let many_keys = vec![keys[0].clone(); 17];
+        assert!(Script::multisig(1, &many_keys).is_err());
+        assert!(Script::multisig(1, &many_keys[..16]).is_ok());
+    }
+
+    #[test]
+    fn test_p2wsh_address() {
+        // P2PK witness script of BIP 173
+        let witness_script = Script::new(vec![
+            Command::push(Secp256k1Point::g().sec_compressed().unwrap()),
+            Command::Op(Opcode::CheckSig),
+        ]);
+        assert_eq!(
+            p2wsh_address(&witness_script, Network::Mainnet).unwrap(),
+            "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3"
+        );
+        assert_eq!(
+            p2wsh_address(&witness_script, Network::Testnet).unwrap(),
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7"
+        );
+        assert!(p2wsh_address(&witness_script, Network::Regtest)
+            .unwrap()
+            .starts_with("bcrt1q"));
+
+        // 16 keys are too many to spend from P2SH, but fit in P2WSH
+        let keys: Vec<Secp256k1Point> = (1..=16u32)
+            .map(|secret| Secp256k1Point::g() * secret)
+            .collect();
+        let script = Script::sorted_multisig(16, &keys).unwrap();
+        assert!(script.to_bytes().len() > MAX_SCRIPT_ELEMENT_SIZE);
+        assert!(p2sh_address(&script, Network::Mainnet).is_err());
+        assert!(p2wsh_address(&script, Network::Mainnet).is_ok());
+    }
+
+    #[test]
+    fn test_checkmultisig() {
+        let keys: Vec<PrivateKey> = (1001..=1003u32)
+            .map(|secret| PrivateKey::new(Secp256k1Felt::new(secret.into())))
+            .collect();
+        let public_keys: Vec<Secp256k1Point> =
+            keys.iter().map(|key| key.public_key().clone()).collect();
+        let flags =
+            VERIFY_STRICTENC | VERIFY_DERSIG | VERIFY_LOW_S | VERIFY_NULLFAIL | VERIFY_NULLDUMMY;
+
+        let script_pubkey = Script::multisig(2, &public_keys).unwrap();
+        let signatures: Vec<Vec<u8>> = keys
+            .iter()
+            .map(|key| sign(key, &script_pubkey, &script_pubkey, 0x01))
+            .collect();
+        let script_sig = |dummy: Vec<u8>, indexes: &[usize]| {
+            let mut commands = vec![Command::push(dummy)];
+            commands.extend(
+                indexes
+                    .iter()
+                    .map(|&index| Command::push(signatures[index].clone())),
+            );
+            Script::new(commands)
+        };
+
+        assert!(verify(&script_sig(vec![], &[0, 2]), &script_pubkey, flags).is_ok());
+        assert!(verify(&script_sig(vec![], &[1, 2]), &script_pubkey, flags).is_ok());
+        // Signatures out of the order of their keys
+        assert!(verify(&script_sig(vec![], &[2, 0]), &script_pubkey, VERIFY_NONE).is_err());
+        assert!(verify(&script_sig(vec![], &[0, 0]), &script_pubkey, VERIFY_NONE).is_err());
+
+        // The dummy element has to be there, and empty with NULLDUMMY
+        let mut no_dummy = script_sig(vec![], &[0, 2]);
+        no_dummy.commands.remove(0);
+        assert!(verify(&no_dummy, &script_pubkey, VERIFY_NONE).is_err());
+        assert!(verify(
+            &script_sig(vec![0x01], &[0, 2]),
+            &script_pubkey,
+            VERIFY_NONE
+        )
+        .is_ok());
+        assert!(verify(
+            &script_sig(vec![0x01], &[0, 2]),
+            &script_pubkey,
+            VERIFY_NULLDUMMY
+        )
+        .is_err());
+
+        // OP_CHECKMULTISIGVERIFY leaves nothing on the stack
+        let mut verify_script = Script::multisig(1, &public_keys).unwrap();
+        *verify_script.commands.last_mut().unwrap() = Command::Op(Opcode::CheckMultiSigVerify);
+        let signature = sign(&keys[1], &verify_script, &verify_script, 0x01);
+        let mut script_sig = Script::new(vec![Command::push(vec![]), Command::push(signature)]);
+        assert!(verify(&script_sig, &verify_script, flags).is_err());
+        script_sig.commands.insert(0, Command::push(vec![0x01]));
+        assert!(verify(&script_sig, &verify_script, flags).is_ok());
+
+        // Failed checks, the signatures have to be empty with NULLFAIL
+        let mut not_script = script_pubkey.clone();
+        not_script.commands.push(Command::Op(Opcode::Not));
+        let wrong_signatures = parse_asm(&format!(
+            "0 0x48 0x{} 0",
+            sign(&keys[0], &script_pubkey, &script_pubkey, 0x01).to_hex()
+        ));
+        assert!(verify(&wrong_signatures, &not_script, VERIFY_NONE).is_ok());
+        assert!(verify(&wrong_signatures, &not_script, VERIFY_NULLFAIL).is_err());
+        assert!(verify(&parse_asm("0 0 0"), &not_script, VERIFY_NULLFAIL).is_ok());
+
+        let cases = [
+            ("0", "0 0 CHECKMULTISIG", true),
+            ("0", "0 0 CHECKMULTISIGVERIFY DEPTH 0 EQUAL", true),
+            ("0", "0 'key' 1 CHECKMULTISIG", true),
+            ("0 0", "1 'key' 1 CHECKMULTISIG NOT", true),
+            ("0", "1 'key' 1 CHECKMULTISIG", false),
+            ("0", "-1 0 CHECKMULTISIG", false),
+            ("0", "0 -1 CHECKMULTISIG", false),
+            ("0 0", "2 'key' 1 CHECKMULTISIG", false),
+        ];
+        for (script_sig, script_pubkey, valid) in cases {
+            let result = verify(
+                &parse_asm(script_sig),
+                &parse_asm(script_pubkey),
+                VERIFY_NONE,
+            );
+            assert_eq!(
+                result.is_ok(),
+                valid,
+                "[{script_sig:?}, {script_pubkey:?}]: {result:?}"
+            );
+        }
+
+        // Each key counts as an opcode, 9 times 0-of-20 is 198 opcodes, 10 times 220
+        let zero_of_twenty = |count| {
+            let mut commands = Vec::new();
+            for _ in 0..count {
+                commands.extend([Command::push(vec![]), Command::push(vec![])]);
+                commands.extend(vec![Command::Op(Opcode::Op1); 20]);
+                commands.extend([
+                    Command::push(encode_num(20)),
+                    Command::Op(Opcode::CheckMultiSig),
+                    Command::Op(Opcode::Drop),
+                ]);
+            }
+            commands.push(Command::Op(Opcode::Op1));
+            Script::new(commands)
+        };
+        assert!(verify(&Script::default(), &zero_of_twenty(9), VERIFY_NONE).is_ok());
+        assert!(verify(&Script::default(), &zero_of_twenty(10), VERIFY_NONE).is_err());
+        assert!(verify(
+            &parse_asm("0 0"),
+            &parse_asm("0 0x01 0x15 CHECKMULTISIG"),
+            VERIFY_NONE
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_p2sh() {
+        let mut keys: Vec<PrivateKey> = (2001..=2003u32)
+            .map(|secret| PrivateKey::new(Secp256k1Felt::new(secret.into())))
+            .collect();
+        let public_keys: Vec<Secp256k1Point> =
+            keys.iter().map(|key| key.public_key().clone()).collect();
+        let redeem_script = Script::sorted_multisig(2, &public_keys).unwrap();
+        let script_pubkey = Script::p2sh(&redeem_script);
+        let flags = VERIFY_P2SH
+            | VERIFY_STRICTENC
+            | VERIFY_DERSIG
+            | VERIFY_LOW_S
+            | VERIFY_NULLFAIL
+            | VERIFY_NULLDUMMY;
+
+        // Signatures follow the sorted keys, and sign the redeem script
+        keys.sort_by_key(public_key);
+        let script_sig = |signed: &Script| {
+            Script::new(vec![
+                Command::push(vec![]),
+                Command::push(sign(&keys[0], &script_pubkey, signed, 0x01)),
+                Command::push(sign(&keys[2], &script_pubkey, signed, 0x01)),
+                Command::push(redeem_script.to_bytes()),
+            ])
+        };
+        assert!(verify(&script_sig(&redeem_script), &script_pubkey, flags).is_ok());
+
+        // Without P2SH only the hash of the redeem script is checked
+        let wrong_signatures = script_sig(&script_pubkey);
+        assert!(verify(&wrong_signatures, &script_pubkey, VERIFY_NONE).is_ok());
+        assert!(verify(&wrong_signatures, &script_pubkey, VERIFY_P2SH).is_err());
+
+        // The script sig has to be push only
+        let mut not_push_only = script_sig(&redeem_script);
+        not_push_only.commands.insert(0, Command::Op(Opcode::Nop));
+        assert!(verify(&not_push_only, &script_pubkey, VERIFY_NONE).is_ok());
+        assert!(verify(&not_push_only, &script_pubkey, VERIFY_P2SH).is_err());
+
+        // Another redeem script
+        let other_redeem_script = Script::sorted_multisig(1, &public_keys).unwrap();
+        let mut other = script_sig(&redeem_script);
+        *other.commands.last_mut().unwrap() = Command::push(other_redeem_script.to_bytes());
+        assert!(verify(&other, &script_pubkey, VERIFY_NONE).is_err());
+
+        // A redeem script that leaves false
+        let script_pubkey = Script::p2sh(&parse_asm("0"));
+        let script_sig = Script::new(vec![Command::push(vec![0x00])]);
+        assert!(verify(&script_sig, &script_pubkey, VERIFY_NONE).is_ok());
+        assert!(verify(&script_sig, &script_pubkey, VERIFY_P2SH).is_err());
+    }
+}
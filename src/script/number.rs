@@ -0,0 +1,74 @@
+use color_eyre::eyre::{eyre, Result};
+
+/// Largest number of bytes arithmetic opcodes take as a number
+pub const MAX_NUM_SIZE: usize = 4;
+
+/// Encodes a number the way scripts keep it on the stack
+///
+/// Little endian magnitude with the sign in the highest bit of the last byte, with
+/// an extra byte if that bit is taken. Zero is the empty vector
+pub fn encode_num(value: i64) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut magnitude = value.unsigned_abs();
+    while magnitude > 0 {
+        bytes.push((magnitude & 0xff) as u8);
+        magnitude >>= 8;
+    }
+
+    if let Some(last) = bytes.last_mut() {
+        if *last & 0x80 != 0 {
+            bytes.push(if value < 0 { 0x80 } else { 0x00 });
+        } else if value < 0 {
+            *last |= 0x80;
+        }
+    }
+
+    bytes
+}
+
+/// Decodes a number of at most `max_size` bytes
+///
+/// With `require_minimal`, encodings with unneeded trailing bytes are rejected, as
+/// the MINIMALDATA rule requires
+///
+/// # Errors
+///
+/// Returns an error if the number is too long or not minimally encoded
+pub fn decode_num(bytes: &[u8], require_minimal: bool, max_size: usize) -> Result<i64> {
+    if bytes.len() > max_size {
+        return Err(eyre!(
+            "Number of {} bytes, at most {max_size} allowed",
+            bytes.len()
+        ));
+    }
+
+    let Some((&last, rest)) = bytes.split_last() else {
+        return Ok(0);
+    };
+
+    // The last byte only holds the sign if the previous byte needs its high bit
+    if require_minimal && last | 0x80 == 0x80 && rest.last().is_none_or(|byte| byte & 0x80 == 0) {
+        return Err(eyre!("Number is not minimally encoded"));
+    }
+
+    let magnitude = bytes
+        .iter()
+        .rev()
+        .fold(0i64, |value, &byte| (value << 8) | i64::from(byte));
+    let sign_bit = 0x80i64 << (8 * (bytes.len() - 1));
+
+    if last & 0x80 == 0 {
+        Ok(magnitude)
+    } else {
+        Ok(-(magnitude & !sign_bit))
+    }
+}
+
+/// Interprets a stack element as a boolean, false for any encoding of zero, negative
+/// zero included
+pub fn cast_to_bool(bytes: &[u8]) -> bool {
+    match bytes.split_last() {
+        None => false,
+        Some((&last, rest)) => rest.iter().any(|&byte| byte != 0) || (last != 0 && last != 0x80),
+    }
+}
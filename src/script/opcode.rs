@@ -0,0 +1,216 @@
+use std::fmt::Display;
+
+/// Defines the opcode enum with its byte and name conversions
+macro_rules! opcodes {
+    ($($variant:ident = $byte:literal, $name:literal;)*) => {
+        /// Named opcodes of Bitcoin Script
+        ///
+        /// The bytes 0x01 to 0x4b push that many bytes and have no names, neither do
+        /// the unassigned bytes 0xbb to 0xfe
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(u8)]
+        pub enum Opcode {
+            $($variant = $byte,)*
+        }
+
+        impl Opcode {
+            /// Returns the opcode of the byte, if it has a name
+            pub fn from_byte(byte: u8) -> Option<Self> {
+                match byte {
+                    $($byte => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns the name of the opcode as Bitcoin Core writes it, like `OP_DUP`
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+opcodes! {
+    // Constants
+    Op0 = 0x00, "OP_0";
+    PushData1 = 0x4c, "OP_PUSHDATA1";
+    PushData2 = 0x4d, "OP_PUSHDATA2";
+    PushData4 = 0x4e, "OP_PUSHDATA4";
+    Op1Negate = 0x4f, "OP_1NEGATE";
+    Reserved = 0x50, "OP_RESERVED";
+    Op1 = 0x51, "OP_1";
+    Op2 = 0x52, "OP_2";
+    Op3 = 0x53, "OP_3";
+    Op4 = 0x54, "OP_4";
+    Op5 = 0x55, "OP_5";
+    Op6 = 0x56, "OP_6";
+    Op7 = 0x57, "OP_7";
+    Op8 = 0x58, "OP_8";
+    Op9 = 0x59, "OP_9";
+    Op10 = 0x5a, "OP_10";
+    Op11 = 0x5b, "OP_11";
+    Op12 = 0x5c, "OP_12";
+    Op13 = 0x5d, "OP_13";
+    Op14 = 0x5e, "OP_14";
+    Op15 = 0x5f, "OP_15";
+    Op16 = 0x60, "OP_16";
+
+    // Flow control
+    Nop = 0x61, "OP_NOP";
+    Ver = 0x62, "OP_VER";
+    If = 0x63, "OP_IF";
+    NotIf = 0x64, "OP_NOTIF";
+    VerIf = 0x65, "OP_VERIF";
+    VerNotIf = 0x66, "OP_VERNOTIF";
+    Else = 0x67, "OP_ELSE";
+    EndIf = 0x68, "OP_ENDIF";
+    Verify = 0x69, "OP_VERIFY";
+    Return = 0x6a, "OP_RETURN";
+
+    // Stack
+    ToAltStack = 0x6b, "OP_TOALTSTACK";
+    FromAltStack = 0x6c, "OP_FROMALTSTACK";
+    TwoDrop = 0x6d, "OP_2DROP";
+    TwoDup = 0x6e, "OP_2DUP";
+    ThreeDup = 0x6f, "OP_3DUP";
+    TwoOver = 0x70, "OP_2OVER";
+    TwoRot = 0x71, "OP_2ROT";
+    TwoSwap = 0x72, "OP_2SWAP";
+    IfDup = 0x73, "OP_IFDUP";
+    Depth = 0x74, "OP_DEPTH";
+    Drop = 0x75, "OP_DROP";
+    Dup = 0x76, "OP_DUP";
+    Nip = 0x77, "OP_NIP";
+    Over = 0x78, "OP_OVER";
+    Pick = 0x79, "OP_PICK";
+    Roll = 0x7a, "OP_ROLL";
+    Rot = 0x7b, "OP_ROT";
+    Swap = 0x7c, "OP_SWAP";
+    Tuck = 0x7d, "OP_TUCK";
+
+    // Splice
+    Cat = 0x7e, "OP_CAT";
+    Substr = 0x7f, "OP_SUBSTR";
+    Left = 0x80, "OP_LEFT";
+    Right = 0x81, "OP_RIGHT";
+    Size = 0x82, "OP_SIZE";
+
+    // Bitwise logic
+    Invert = 0x83, "OP_INVERT";
+    And = 0x84, "OP_AND";
+    Or = 0x85, "OP_OR";
+    Xor = 0x86, "OP_XOR";
+    Equal = 0x87, "OP_EQUAL";
+    EqualVerify = 0x88, "OP_EQUALVERIFY";
+    Reserved1 = 0x89, "OP_RESERVED1";
+    Reserved2 = 0x8a, "OP_RESERVED2";
+
+    // Arithmetic
+    OneAdd = 0x8b, "OP_1ADD";
+    OneSub = 0x8c, "OP_1SUB";
+    TwoMul = 0x8d, "OP_2MUL";
+    TwoDiv = 0x8e, "OP_2DIV";
+    Negate = 0x8f, "OP_NEGATE";
+    Abs = 0x90, "OP_ABS";
+    Not = 0x91, "OP_NOT";
+    ZeroNotEqual = 0x92, "OP_0NOTEQUAL";
+    Add = 0x93, "OP_ADD";
+    Sub = 0x94, "OP_SUB";
+    Mul = 0x95, "OP_MUL";
+    Div = 0x96, "OP_DIV";
+    Mod = 0x97, "OP_MOD";
+    LShift = 0x98, "OP_LSHIFT";
+    RShift = 0x99, "OP_RSHIFT";
+    BoolAnd = 0x9a, "OP_BOOLAND";
+    BoolOr = 0x9b, "OP_BOOLOR";
+    NumEqual = 0x9c, "OP_NUMEQUAL";
+    NumEqualVerify = 0x9d, "OP_NUMEQUALVERIFY";
+    NumNotEqual = 0x9e, "OP_NUMNOTEQUAL";
+    LessThan = 0x9f, "OP_LESSTHAN";
+    GreaterThan = 0xa0, "OP_GREATERTHAN";
+    LessThanOrEqual = 0xa1, "OP_LESSTHANOREQUAL";
+    GreaterThanOrEqual = 0xa2, "OP_GREATERTHANOREQUAL";
+    Min = 0xa3, "OP_MIN";
+    Max = 0xa4, "OP_MAX";
+    Within = 0xa5, "OP_WITHIN";
+
+    // Crypto
+    Ripemd160 = 0xa6, "OP_RIPEMD160";
+    Sha1 = 0xa7, "OP_SHA1";
+    Sha256 = 0xa8, "OP_SHA256";
+    Hash160 = 0xa9, "OP_HASH160";
+    Hash256 = 0xaa, "OP_HASH256";
+    CodeSeparator = 0xab, "OP_CODESEPARATOR";
+    CheckSig = 0xac, "OP_CHECKSIG";
+    CheckSigVerify = 0xad, "OP_CHECKSIGVERIFY";
+    CheckMultiSig = 0xae, "OP_CHECKMULTISIG";
+    CheckMultiSigVerify = 0xaf, "OP_CHECKMULTISIGVERIFY";
+
+    // Expansion
+    Nop1 = 0xb0, "OP_NOP1";
+    CheckLockTimeVerify = 0xb1, "OP_CHECKLOCKTIMEVERIFY";
+    CheckSequenceVerify = 0xb2, "OP_CHECKSEQUENCEVERIFY";
+    Nop4 = 0xb3, "OP_NOP4";
+    Nop5 = 0xb4, "OP_NOP5";
+    Nop6 = 0xb5, "OP_NOP6";
+    Nop7 = 0xb6, "OP_NOP7";
+    Nop8 = 0xb7, "OP_NOP8";
+    Nop9 = 0xb8, "OP_NOP9";
+    Nop10 = 0xb9, "OP_NOP10";
+    CheckSigAdd = 0xba, "OP_CHECKSIGADD";
+
+    InvalidOpcode = 0xff, "OP_INVALIDOPCODE";
+}
+
+impl Opcode {
+    /// Returns the byte of the opcode
+    pub fn to_byte(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the number pushed by `OP_0` and `OP_1` to `OP_16`
+    pub fn small_int(self) -> Option<u8> {
+        match self {
+            Self::Op0 => Some(0),
+            Self::Op1
+            | Self::Op2
+            | Self::Op3
+            | Self::Op4
+            | Self::Op5
+            | Self::Op6
+            | Self::Op7
+            | Self::Op8
+            | Self::Op9
+            | Self::Op10
+            | Self::Op11
+            | Self::Op12
+            | Self::Op13
+            | Self::Op14
+            | Self::Op15
+            | Self::Op16 => Some(self.to_byte() - Self::Op1.to_byte() + 1),
+            _ => None,
+        }
+    }
+
+    /// Returns the opcode pushing the number, `OP_0` or `OP_1` to `OP_16`
+    pub fn from_small_int(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Op0),
+            1..=16 => Self::from_byte(Self::Op1.to_byte() + value - 1),
+            _ => None,
+        }
+    }
+
+    /// Checks if the opcode pushes data, `OP_0` and the `OP_PUSHDATA`s
+    pub fn is_push(self) -> bool {
+        self.to_byte() <= Self::PushData4.to_byte()
+    }
+}
+
+impl Display for Opcode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -0,0 +1,181 @@
+use super::{
+    command::{Command, PushKind},
+    number::encode_num,
+    opcode::Opcode,
+};
+use crate::{
+    elliptic_curve::secp256k1::Secp256k1Point,
+    encoding::encodable::{Decodable, Encodable},
+    helpers::hash::hash160,
+};
+use color_eyre::eyre::{eyre, Result};
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Display,
+    io::{Cursor, Read, Write},
+};
+
+/// Most keys a multisig script built by `Script::multisig` can have, the largest
+/// number `OP_16` pushes
+pub const MAX_MULTISIG_KEYS: usize = 16;
+
+/// A Bitcoin script, the list of its commands
+///
+/// Used for both `scriptPubKey`s, which lock outputs, and `scriptSig`s, which
+/// unlock them. Transactions keep scripts as raw bytes, as they don't have to parse,
+/// use `Script::parse` to inspect them
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Script {
+    pub commands: Vec<Command>,
+}
+
+impl Script {
+    pub fn new(commands: Vec<Command>) -> Self {
+        Self { commands }
+    }
+
+    /// Parses the raw bytes of a script, without a length prefix
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a push runs past the end of the script
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let mut cursor = Cursor::new(bytes);
+        let mut commands = Vec::new();
+
+        while cursor.position() < bytes.len() as u64 {
+            let position = cursor.position();
+            let command = Command::decode(&mut cursor)
+                .map_err(|error| eyre!("Invalid command at byte {position}: {error}"))?;
+            commands.push(command);
+        }
+
+        Ok(Self::new(commands))
+    }
+
+    /// Returns the raw bytes of the script, without a length prefix
+    ///
+    /// # Panics
+    ///
+    /// Panics if a push can't encode the length of its data
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for command in &self.commands {
+            command
+                .encode(&mut bytes)
+                .expect("Push with a length its kind can't encode");
+        }
+        bytes
+    }
+
+    /// Builds the m-of-n multisig script `<m> <public keys> <n> OP_CHECKMULTISIG`,
+    /// keeping the order of the keys
+    ///
+    /// Keys are compressed SEC, m and n are pushed with `OP_1` to `OP_16`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if m is not in [1, n], n is above 16 or a key is at infinity
+    pub fn multisig(required: usize, public_keys: &[Secp256k1Point]) -> Result<Self> {
+        let total = public_keys.len();
+        if total > MAX_MULTISIG_KEYS || !(1..=total).contains(&required) {
+            return Err(eyre!("Invalid {required}-of-{total} multisig"));
+        }
+
+        let mut commands = vec![Command::push(encode_num(i64::try_from(required)?))];
+        for public_key in public_keys {
+            let sec = public_key
+                .sec_compressed()
+                .ok_or_else(|| eyre!("Public key at infinity"))?;
+            commands.push(Command::push(sec));
+        }
+        commands.push(Command::push(encode_num(i64::try_from(total)?)));
+        commands.push(Command::Op(Opcode::CheckMultiSig));
+        Ok(Self::new(commands))
+    }
+
+    /// Builds the multisig script of `multisig` with the keys sorted by their
+    /// compressed SEC encoding (BIP 67), so the script doesn't depend on the order
+    /// the keys are given in
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if m is not in [1, n], n is above 16 or a key is at infinity
+    pub fn sorted_multisig(required: usize, public_keys: &[Secp256k1Point]) -> Result<Self> {
+        let mut script = Self::multisig(required, public_keys)?;
+        let keys = 1..=public_keys.len();
+        script.commands[keys].sort_by(|a, b| a.data().cmp(&b.data()));
+        Ok(script)
+    }
+
+    /// Builds the P2SH script pubkey `OP_HASH160 <hash160 of the redeem script>
+    /// OP_EQUAL` (BIP 16)
+    pub fn p2sh(redeem_script: &Script) -> Self {
+        Self::new(vec![
+            Command::Op(Opcode::Hash160),
+            Command::push(hash160(&redeem_script.to_bytes()).to_vec()),
+            Command::Op(Opcode::Equal),
+        ])
+    }
+
+    /// Builds the P2WSH script pubkey `OP_0 <sha256 of the witness script>` (BIP 141)
+    pub fn p2wsh(witness_script: &Script) -> Self {
+        Self::new(vec![
+            Command::push(Vec::new()),
+            Command::push(Sha256::digest(witness_script.to_bytes()).to_vec()),
+        ])
+    }
+
+    /// Checks if the script is a P2SH script pubkey, which has to be exactly
+    /// `OP_HASH160 <20 bytes> OP_EQUAL`
+    pub fn is_p2sh(&self) -> bool {
+        matches!(
+            &self.commands[..],
+            [
+                Command::Op(Opcode::Hash160),
+                Command::Push { kind: PushKind::Direct, data },
+                Command::Op(Opcode::Equal),
+            ] if data.len() == 20
+        )
+    }
+
+    /// Checks if the script only pushes data, which is required of `scriptSig`s
+    pub fn is_push_only(&self) -> bool {
+        self.commands.iter().all(Command::is_push)
+    }
+
+    /// Checks if every push uses the smallest encoding for its data
+    pub fn has_minimal_pushes(&self) -> bool {
+        self.commands.iter().all(Command::is_minimal_push)
+    }
+}
+
+impl Encodable for Script {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let mut bytes = Vec::new();
+        for command in &self.commands {
+            command.encode(&mut bytes)?;
+        }
+        bytes.encode(writer)
+    }
+}
+
+impl Decodable for Script {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        Self::parse(&Vec::<u8>::decode(reader)?)
+    }
+}
+
+/// Writes the script in the assembly format, like
+/// `OP_DUP OP_HASH160 <hex> OP_EQUALVERIFY OP_CHECKSIG`
+impl Display for Script {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, command) in self.commands.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{command}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use crate::encoding::encodable::{Decodable, Encodable};
+use color_eyre::eyre::Result;
+use std::io::{Read, Write};
+
+/// Points to an output of a previous transaction
+///
+/// The txid is kept in the byte order it is serialized in, which is the reverse of
+/// how it is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OutPoint {
+    pub txid: [u8; 32],
+    pub vout: u32,
+}
+
+impl OutPoint {
+    pub fn new(txid: [u8; 32], vout: u32) -> Self {
+        Self { txid, vout }
+    }
+
+    /// The outpoint of coinbase inputs, which spend nothing
+    pub fn null() -> Self {
+        Self::new([0; 32], u32::MAX)
+    }
+
+    pub fn is_null(&self) -> bool {
+        *self == Self::null()
+    }
+}
+
+impl Encodable for OutPoint {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        Ok(self.txid.encode(writer)? + self.vout.encode(writer)?)
+    }
+}
+
+impl Decodable for OutPoint {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        Ok(Self::new(
+            Decodable::decode(reader)?,
+            Decodable::decode(reader)?,
+        ))
+    }
+}
+
+/// Represents a transaction input
+///
+/// - `previous_output`: the output being spent
+/// - `script_sig`: the script unlocking it
+/// - `sequence`: used for relative locktimes and replace-by-fee
+/// - `witness`: the witness stack of segwit inputs (BIP 141), empty for legacy ones
+///
+/// The witness is not part of the encoding of the input, `Tx` writes it after the
+/// outputs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxIn {
+    pub previous_output: OutPoint,
+    pub script_sig: Vec<u8>,
+    pub sequence: u32,
+    pub witness: Vec<Vec<u8>>,
+}
+
+impl TxIn {
+    pub fn new(previous_output: OutPoint, script_sig: Vec<u8>, sequence: u32) -> Self {
+        Self {
+            previous_output,
+            script_sig,
+            sequence,
+            witness: Vec::new(),
+        }
+    }
+
+    /// Sets the witness stack of the input
+    pub fn with_witness(mut self, witness: Vec<Vec<u8>>) -> Self {
+        self.witness = witness;
+        self
+    }
+}
+
+impl Encodable for TxIn {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        Ok(self.previous_output.encode(writer)?
+            + self.script_sig.encode(writer)?
+            + self.sequence.encode(writer)?)
+    }
+}
+
+impl Decodable for TxIn {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        Ok(Self::new(
+            Decodable::decode(reader)?,
+            Decodable::decode(reader)?,
+            Decodable::decode(reader)?,
+        ))
+    }
+}
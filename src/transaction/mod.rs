@@ -0,0 +1,682 @@
+#![allow(unused)]
+pub mod input;
+pub mod output;
+pub mod sighash;
+pub mod tx;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        input::{OutPoint, TxIn},
+        output::TxOut,
+        sighash::{
+            tap_leaf_hash, ScriptPath, Sighash, SighashCache, SIGHASH_ALL, SIGHASH_ANYONECANPAY,
+            SIGHASH_DEFAULT, SIGHASH_NONE, SIGHASH_SINGLE, TAPSCRIPT_LEAF_VERSION,
+        },
+        tx::Tx,
+    };
+    use crate::{
+        elliptic_curve::{
+            private_key::PrivateKey,
+            secp256k1::{Secp256k1Felt, Secp256k1Point},
+            signature::Signature,
+        },
+        encoding::encodable::{Decodable, Encodable},
+        helpers::bytes::{ToBytesBigEndian, ToHex},
+    };
+    use num_bigint::BigUint;
+
+    /// Transaction of chapter 5 of Programming Bitcoin
+    const TX_HEX: &str = "0100000001813f79011acb80925dfe69b3def355fe914bd1d96a3f5f71bf8303c6a989c7d1000000006b483045022100ed81ff192e75a3fd2304004dcadb746fa5e24c5031ccfcf21320b0277457c98f02207a986d955c6e0cb35d446a89d3f56100f4d7f67801c31967743a9c8e10615bed01210349fc4e631e3624a545de3f89f5d8684c7b8138bd94bdd531d2e213bf016b278afeffffff02a135ef01000000001976a914bc3b654dca7e56b04dca18f2566cdaf02e8d9ada88ac99c39800000000001976a9141c4bc762dd5423e332166702cb75f40df79fea1288ac19430600";
+
+    /// Coinbase transaction of the genesis block
+    const GENESIS_COINBASE_HEX: &str = "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000";
+
+    /// Signed transaction of the native P2WPKH example of BIP 143, the first input is
+    /// legacy and the second one is segwit
+    const SEGWIT_TX_HEX: &str = "01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000";
+
+    #[test]
+    fn test_parse_tx() {
+        let bytes = TX_HEX.to_bytes_be();
+        let tx = Tx::deserialize(&bytes).unwrap();
+
+        assert_eq!(tx.version, 1);
+        assert_eq!(tx.locktime, 410_393);
+
+        assert_eq!(tx.inputs.len(), 1);
+        let input = &tx.inputs[0];
+        let mut previous_txid = input.previous_output.txid;
+        previous_txid.reverse();
+        assert_eq!(
+            previous_txid.to_hex(),
+            "d1c789a9c60383bf715f3f6ad9d14b91fe55f3deb369fe5d9280cb1a01793f81"
+        );
+        assert_eq!(input.previous_output.vout, 0);
+        assert_eq!(input.script_sig.len(), 0x6b);
+        assert_eq!(input.sequence, 0xffff_fffe);
+
+        assert_eq!(tx.outputs.len(), 2);
+        assert_eq!(tx.outputs[0].value, 32_454_049);
+        assert_eq!(
+            tx.outputs[0].script_pubkey.to_hex(),
+            "76a914bc3b654dca7e56b04dca18f2566cdaf02e8d9ada88ac"
+        );
+        assert_eq!(tx.outputs[1].value, 10_011_545);
+
+        assert_eq!(tx.serialize(), bytes);
+        assert!(!tx.is_coinbase());
+        assert_eq!(
+            tx.id(),
+            "452c629d67e41baec3ac6f04fe744b4b9617f8f859c63b3002f8684e7a4fee03"
+        );
+    }
+
+    #[test]
+    fn test_genesis_coinbase() {
+        let bytes = GENESIS_COINBASE_HEX.to_bytes_be();
+        let tx = Tx::deserialize(&bytes).unwrap();
+
+        assert!(tx.is_coinbase());
+        assert_eq!(tx.outputs[0].value, 50 * 100_000_000);
+        assert_eq!(tx.serialize(), bytes);
+        assert_eq!(
+            tx.id(),
+            "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"
+        );
+    }
+
+    #[test]
+    fn test_build_tx() {
+        let tx = Tx::new(
+            2,
+            vec![TxIn::new(
+                OutPoint::new([1; 32], 3),
+                vec![0x51],
+                0xffff_ffff,
+            )],
+            vec![TxOut::new(1000, vec![0x6a])],
+            0,
+        );
+
+        let bytes = tx.serialize();
+        assert_eq!(bytes.len(), 4 + 1 + 32 + 4 + 2 + 4 + 1 + 8 + 2 + 4);
+        assert_eq!(Tx::deserialize(&bytes).unwrap(), tx);
+        assert_eq!(tx.txid().len(), 32);
+    }
+
+    #[test]
+    fn test_parse_invalid_tx() {
+        let mut bytes = TX_HEX.to_bytes_be();
+
+        // Truncated
+        assert!(Tx::deserialize(&bytes[..bytes.len() - 1]).is_err());
+
+        // Trailing data
+        bytes.push(0);
+        assert!(Tx::deserialize(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_segwit_tx() {
+        let bytes = SEGWIT_TX_HEX.to_bytes_be();
+        let tx = Tx::deserialize(&bytes).unwrap();
+
+        assert!(tx.has_witness());
+        assert_eq!(tx.inputs.len(), 2);
+        assert!(tx.inputs[0].witness.is_empty());
+        assert_eq!(tx.inputs[1].witness.len(), 2);
+        assert_eq!(tx.inputs[1].witness[0].len(), 0x47);
+        assert_eq!(
+            tx.inputs[1].witness[1].to_hex(),
+            "025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee6357"
+        );
+        assert_eq!(tx.locktime, 0x11);
+        assert_eq!(tx.serialize(), bytes);
+
+        assert_eq!(
+            tx.id(),
+            "e8151a2af31c368a35053ddd4bdb285a8595c769a3ad83e0fa02314a602d4609"
+        );
+        assert_eq!(
+            tx.witness_id(),
+            "c36c38370907df2324d9ce9d149d191192f338b37665a82e78e76a12c909b762"
+        );
+
+        assert_eq!(tx.base_size(), 233);
+        assert_eq!(tx.total_size(), 343);
+        assert_eq!(tx.weight(), 1042);
+        assert_eq!(tx.vsize(), 261);
+
+        // Without witness it's a legacy transaction with the same txid
+        let stripped = Tx::deserialize(&tx.serialize_without_witness()).unwrap();
+        assert!(!stripped.has_witness());
+        assert_eq!(stripped.txid(), tx.txid());
+        assert_eq!(stripped.wtxid(), stripped.txid());
+    }
+
+    #[test]
+    fn test_legacy_tx_sizes() {
+        let tx = Tx::deserialize(&TX_HEX.to_bytes_be()).unwrap();
+
+        assert_eq!(tx.base_size(), tx.total_size());
+        assert_eq!(tx.weight(), tx.total_size() * 4);
+        assert_eq!(tx.vsize(), tx.total_size());
+        assert_eq!(tx.wtxid(), tx.txid());
+    }
+
+    #[test]
+    fn test_parse_invalid_segwit_tx() {
+        let bytes = SEGWIT_TX_HEX.to_bytes_be();
+
+        // Unknown flag
+        let mut unknown_flag = bytes.clone();
+        unknown_flag[5] = 0x02;
+        assert!(Tx::deserialize(&unknown_flag).is_err());
+
+        // Marker and flag, but every witness is empty
+        let mut tx = Tx::deserialize(&bytes).unwrap();
+        tx.inputs[1].witness.clear();
+        let mut empty_witness = tx.version.serialize();
+        empty_witness.extend_from_slice(&[0, 1]);
+        empty_witness.extend_from_slice(&tx.inputs.serialize());
+        empty_witness.extend_from_slice(&tx.outputs.serialize());
+        empty_witness.extend_from_slice(&[0, 0]);
+        empty_witness.extend_from_slice(&tx.locktime.serialize());
+        assert!(Tx::deserialize(&empty_witness).is_err());
+
+        // Truncated witness
+        assert!(Tx::deserialize(&bytes[..bytes.len() - 10]).is_err());
+    }
+
+    #[test]
+    fn test_build_segwit_tx() {
+        let input = TxIn::new(OutPoint::new([2; 32], 0), vec![], 0xffff_fffd)
+            .with_witness(vec![vec![0xaa; 72], vec![0x02; 33]]);
+        let tx = Tx::new(2, vec![input], vec![TxOut::new(5000, vec![0x51])], 0);
+
+        let bytes = tx.serialize();
+        assert_eq!(&bytes[4..6], &[0, 1]);
+        assert_eq!(Tx::deserialize(&bytes).unwrap(), tx);
+        assert_ne!(tx.wtxid(), tx.txid());
+        assert!(tx.vsize() < tx.total_size());
+    }
+
+    /// Script pubkey of the output spent by the transaction of chapter 5
+    const TX_PREVIOUS_SCRIPT_PUBKEY: &str = "76a914a802fc56c704ce87c42d7c92eb75e7896bdc41ae88ac";
+
+    /// Transaction with 3 inputs and 2 outputs to compare signature hash types
+    fn sighash_tx() -> Tx {
+        let inputs = (0..3)
+            .map(|index| TxIn::new(OutPoint::new([index; 32], u32::from(index)), vec![0x51], 7))
+            .collect();
+        let outputs = vec![TxOut::new(1000, vec![0x6a]), TxOut::new(2000, vec![0x52])];
+        Tx::new(1, inputs, outputs, 0)
+    }
+
+    #[test]
+    fn test_legacy_sighash() {
+        let tx = Tx::deserialize(&TX_HEX.to_bytes_be()).unwrap();
+        let script_code = TX_PREVIOUS_SCRIPT_PUBKEY.to_bytes_be();
+
+        let sighash = tx.legacy_sighash(0, &script_code, SIGHASH_ALL);
+        assert_eq!(
+            sighash.0.to_hex(),
+            "27e0c5994dec7824e56dec6b2fcb342eb7cdb0d0957c2fce9882f715e85d81a6"
+        );
+
+        // The script sig pushes the DER signature with its hash type and the public key
+        let script_sig = &tx.inputs[0].script_sig;
+        let der = &script_sig[1..=72];
+        assert_eq!(der[71], 0x01);
+        let signature = Signature::try_new(
+            BigUint::from_bytes_be(&der[5..37]),
+            BigUint::from_bytes_be(&der[39..71]),
+        )
+        .unwrap();
+        let public_key = Secp256k1Point::sec_parse(&script_sig[74..]);
+        assert!(signature.verify(&sighash.z(), &public_key));
+
+        // Another hash type doesn't verify
+        let none = tx.legacy_sighash(0, &script_code, SIGHASH_NONE);
+        assert!(!signature.verify(&none.z(), &public_key));
+    }
+
+    #[test]
+    fn test_legacy_sighash_core_vectors() {
+        // Bitcoin Core's sighash.json, random transactions and script codes with
+        // [raw transaction, script code, input index, hash type, sighash]
+        let data: serde_json::Value =
+            serde_json::from_str(include_str!("../../test_data/bitcoin_core/sighash.json"))
+                .unwrap();
+
+        // The first entry is the header
+        for entry in data.as_array().unwrap().iter().skip(1) {
+            let tx = Tx::deserialize(&entry[0].as_str().unwrap().to_bytes_be()).unwrap();
+            let script_code = entry[1].as_str().unwrap().to_bytes_be();
+            let input_index = usize::try_from(entry[2].as_u64().unwrap()).unwrap();
+            // Signed 32 bit integers in the file
+            let hash_type = i32::try_from(entry[3].as_i64().unwrap())
+                .unwrap()
+                .cast_unsigned();
+
+            let sighash = tx.legacy_sighash(input_index, &script_code, hash_type);
+            assert_eq!(sighash.to_display(), entry[4].as_str().unwrap(), "{entry}");
+        }
+    }
+
+    #[test]
+    fn test_legacy_sighash_types() {
+        let tx = sighash_tx();
+        let script_code = [0x76, 0xac];
+        let hashes = |tx: &Tx, hash_type| -> Vec<Sighash> {
+            (0..tx.inputs.len())
+                .map(|index| tx.legacy_sighash(index, &script_code, hash_type))
+                .collect()
+        };
+
+        // Changing another input's script doesn't change the hashes
+        let mut changed_script = tx.clone();
+        changed_script.inputs[2].script_sig = vec![0x00];
+        assert_eq!(
+            hashes(&tx, SIGHASH_ALL),
+            hashes(&changed_script, SIGHASH_ALL)
+        );
+
+        // ALL commits to every output, NONE to none of them
+        let mut changed_output = tx.clone();
+        changed_output.outputs[1].value += 1;
+        let all = hashes(&tx, SIGHASH_ALL);
+        assert!(all
+            .iter()
+            .zip(hashes(&changed_output, SIGHASH_ALL))
+            .all(|(a, b)| *a != b));
+        assert_eq!(
+            hashes(&tx, SIGHASH_NONE),
+            hashes(&changed_output, SIGHASH_NONE)
+        );
+
+        // SINGLE commits only to the output with the same index
+        let single = hashes(&tx, SIGHASH_SINGLE);
+        let changed_single = hashes(&changed_output, SIGHASH_SINGLE);
+        assert_eq!(single[0], changed_single[0]);
+        assert_ne!(single[1], changed_single[1]);
+
+        // NONE and SINGLE don't commit to the sequence of the other inputs
+        let mut changed_sequence = tx.clone();
+        changed_sequence.inputs[1].sequence = 0;
+        for hash_type in [SIGHASH_NONE, SIGHASH_SINGLE] {
+            let before = hashes(&tx, hash_type);
+            let after = hashes(&changed_sequence, hash_type);
+            assert_eq!(before[0], after[0]);
+            assert_ne!(before[1], after[1]);
+        }
+        assert_ne!(
+            hashes(&tx, SIGHASH_ALL)[0],
+            hashes(&changed_sequence, SIGHASH_ALL)[0]
+        );
+
+        // ANYONECANPAY only commits to the signed input
+        let mut extra_input = tx.clone();
+        extra_input
+            .inputs
+            .push(TxIn::new(OutPoint::new([9; 32], 0), vec![], 0));
+        for hash_type in [SIGHASH_ALL, SIGHASH_NONE, SIGHASH_SINGLE] {
+            let hash_type = hash_type | SIGHASH_ANYONECANPAY;
+            assert_eq!(
+                hashes(&tx, hash_type)[..2],
+                hashes(&extra_input, hash_type)[..2]
+            );
+        }
+        assert_ne!(
+            hashes(&tx, SIGHASH_ALL)[0],
+            hashes(&extra_input, SIGHASH_ALL)[0]
+        );
+
+        // Every hash type gives a different hash
+        let mut all_types = Vec::new();
+        for hash_type in [SIGHASH_ALL, SIGHASH_NONE, SIGHASH_SINGLE] {
+            all_types.push(tx.legacy_sighash(0, &script_code, hash_type));
+            all_types.push(tx.legacy_sighash(0, &script_code, hash_type | SIGHASH_ANYONECANPAY));
+        }
+        for (index, hash) in all_types.iter().enumerate() {
+            assert!(!all_types[index + 1..].contains(hash));
+        }
+    }
+
+    #[test]
+    fn test_legacy_sighash_single_bug() {
+        let tx = sighash_tx();
+
+        // The third input has no matching output, so 1 is signed
+        let sighash = tx.legacy_sighash(2, &[0xac], SIGHASH_SINGLE);
+        assert_eq!(sighash, Sighash::one());
+        assert_eq!(
+            sighash.to_display(),
+            "0000000000000000000000000000000000000000000000000000000000000001"
+        );
+        assert_eq!(
+            tx.legacy_sighash(2, &[0xac], SIGHASH_SINGLE | SIGHASH_ANYONECANPAY),
+            Sighash::one()
+        );
+        assert_eq!(tx.legacy_sighash(3, &[0xac], SIGHASH_ALL), Sighash::one());
+        assert_ne!(tx.legacy_sighash(2, &[0xac], SIGHASH_ALL), Sighash::one());
+
+        // Such a signature is valid for any transaction
+        let key = PrivateKey::new(Secp256k1Felt::new(12345u32.into()));
+        let signature = key.sign(&sighash.z());
+        let mut other = tx.clone();
+        other.outputs.clear();
+        let other_sighash = other.legacy_sighash(0, &[0xac], SIGHASH_SINGLE);
+        assert!(signature.verify(&other_sighash.z(), key.public_key()));
+    }
+
+    #[test]
+    fn test_legacy_sighash_codeseparator() {
+        let tx = sighash_tx();
+        let sighash = |script_code: &[u8]| tx.legacy_sighash(0, script_code, SIGHASH_ALL);
+
+        // OP_CODESEPARATORs are removed from the script code
+        assert_eq!(
+            sighash(&[0xab, 0x76, 0xab, 0xac, 0xab]),
+            sighash(&[0x76, 0xac])
+        );
+
+        // But not from pushed data
+        let push = [0x02, 0xab, 0xab, 0xac];
+        assert_ne!(sighash(&push), sighash(&[0x02, 0xac]));
+        assert_eq!(
+            sighash(&[0xab, 0x02, 0xab, 0xab, 0xab, 0xac]),
+            sighash(&push)
+        );
+        assert_eq!(
+            sighash(&[0x4c, 0x01, 0xab, 0xab]),
+            sighash(&[0x4c, 0x01, 0xab])
+        );
+
+        // A push past the end of the script is kept as it is
+        assert_ne!(sighash(&[0xab, 0x05, 0xab]), sighash(&[0x05]));
+        assert_eq!(sighash(&[0xab, 0x05, 0xab]), sighash(&[0x05, 0xab]));
+    }
+
+    /// Parses a DER signature followed by its hash type, as pushed by scripts
+    fn parse_der(bytes: &[u8]) -> Signature {
+        let r_length = usize::from(bytes[3]);
+        let r = &bytes[4..4 + r_length];
+        let s = &bytes[6 + r_length..bytes.len() - 1];
+        Signature::try_new(BigUint::from_bytes_be(r), BigUint::from_bytes_be(s)).unwrap()
+    }
+
+    #[test]
+    fn test_segwit_v0_sighash_p2wpkh() {
+        let tx = Tx::deserialize(&SEGWIT_TX_HEX.to_bytes_be()).unwrap();
+        let cache = SighashCache::new(&tx);
+
+        // The second input spends 6 BTC of a P2WPKH output
+        let script_code = "76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac".to_bytes_be();
+        let sighash = cache
+            .segwit_v0_sighash(1, &script_code, 600_000_000, SIGHASH_ALL)
+            .unwrap();
+        assert_eq!(
+            sighash.0.to_hex(),
+            "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670"
+        );
+        assert_eq!(
+            tx.segwit_v0_sighash(1, &script_code, 600_000_000, SIGHASH_ALL)
+                .unwrap(),
+            sighash
+        );
+
+        let witness = &tx.inputs[1].witness;
+        let public_key = Secp256k1Point::sec_parse(&witness[1]);
+        assert!(parse_der(&witness[0]).verify(&sighash.z(), &public_key));
+
+        // The amount is committed to
+        let other_amount = cache
+            .segwit_v0_sighash(1, &script_code, 600_000_001, SIGHASH_ALL)
+            .unwrap();
+        assert!(!parse_der(&witness[0]).verify(&other_amount.z(), &public_key));
+
+        // The first input is a legacy P2PK spend signing the legacy hash
+        let script_pubkey =
+            "2103c9f4836b9a4f77fc0d81f7bcb01b7f1b35916864b9476c241ce9fc198bd25432ac".to_bytes_be();
+        let legacy = tx.legacy_sighash(0, &script_pubkey, SIGHASH_ALL);
+        let public_key = Secp256k1Point::sec_parse(&script_pubkey[1..34]);
+        let script_sig = &tx.inputs[0].script_sig;
+        assert!(parse_der(&script_sig[1..]).verify(&legacy.z(), &public_key));
+
+        assert!(cache
+            .segwit_v0_sighash(2, &script_code, 0, SIGHASH_ALL)
+            .is_err());
+    }
+
+    #[test]
+    fn test_segwit_v0_sighash_p2sh_p2wpkh() {
+        let tx = Tx::deserialize(&"0100000001db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a54770100000000feffffff02b8b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac0008af2f000000001976a914fd270b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac92040000".to_bytes_be()).unwrap();
+        let script_code = "76a91479091972186c449eb1ded22b78e40d009bdf008988ac".to_bytes_be();
+
+        let sighash = tx
+            .segwit_v0_sighash(0, &script_code, 1_000_000_000, SIGHASH_ALL)
+            .unwrap();
+        assert_eq!(
+            sighash.0.to_hex(),
+            "64f3b0f4dd2bb3aa1ce8566d220cc74dda9df97d8490cc81d89d735c92e59fb6"
+        );
+    }
+
+    #[test]
+    fn test_segwit_v0_sighash_types() {
+        // 6-of-6 P2SH-P2WSH multisig example, signed with every hash type
+        let tx = Tx::deserialize(&"010000000136641869ca081e70f394c6948e8af409e18b619df2ed74aa106c1ca29787b96e0100000000ffffffff0200e9a435000000001976a914389ffce9cd9ae88dcc0631e88a821ffdbe9bfe2688acc0832f05000000001976a9147480a33f950689af511e6e84c138dbbd3c3ee41588ac00000000".to_bytes_be()).unwrap();
+        let witness_script = "56210307b8ae49ac90a048e9b53357a2354b3334e9c8bee813ecb98e99a7e07e8c3ba32103b28f0c28bfab54554ae8c658ac5c3e0ce6e79ad336331f78c428dd43eea8449b21034b8113d703413d57761b8b9781957b8c0ac1dfe69f492580ca4195f50376ba4a21033400f6afecb833092a9a21cfdf1ed1376e58c5d1f47de74683123987e967a8f42103a6d48b1131e94ba04d9737d61acdaa1322008af9602b3b14862c07a1789aac162102d8b661b0b3302ee2f162b09e07a55ad5dfbe673a9f01d9f0c19617681024306b56ae".to_bytes_be();
+        let cache = SighashCache::new(&tx);
+
+        let expected = [
+            (
+                SIGHASH_ALL,
+                "185c0be5263dce5b4bb50a047973c1b6272bfbd0103a89444597dc40b248ee7c",
+            ),
+            (
+                SIGHASH_NONE,
+                "e9733bc60ea13c95c6527066bb975a2ff29a925e80aa14c213f686cbae5d2f36",
+            ),
+            (
+                SIGHASH_SINGLE,
+                "1e1f1c303dc025bd664acb72e583e933fae4cff9148bf78c157d1e8f78530aea",
+            ),
+            (
+                SIGHASH_ALL | SIGHASH_ANYONECANPAY,
+                "2a67f03e63a6a422125878b40b82da593be8d4efaafe88ee528af6e5a9955c6e",
+            ),
+            (
+                SIGHASH_NONE | SIGHASH_ANYONECANPAY,
+                "781ba15f3779d5542ce8ecb5c18716733a5ee42a6f51488ec96154934e2c890a",
+            ),
+            (
+                SIGHASH_SINGLE | SIGHASH_ANYONECANPAY,
+                "511e8e52ed574121fc1b654970395502128263f62662e076dc6baf05c2e6a99b",
+            ),
+        ];
+        for (hash_type, sighash) in expected {
+            assert_eq!(
+                cache
+                    .segwit_v0_sighash(0, &witness_script, 987_654_321, hash_type)
+                    .unwrap()
+                    .0
+                    .to_hex(),
+                sighash
+            );
+        }
+    }
+
+    /// Unsigned transaction of the key path spending vectors of BIP 341
+    const TAPROOT_TX_HEX: &str = "02000000097de20cbff686da83a54981d2b9bab3586f4ca7e48f57f5b55963115f3b334e9c010000000000000000d7b7cab57b1393ace2d064f4d4a2cb8af6def61273e127517d44759b6dafdd990000000000fffffffff8e1f583384333689228c5d28eac13366be082dc57441760d957275419a418420000000000fffffffff0689180aa63b30cb162a73c6d2a38b7eeda2a83ece74310fda0843ad604853b0100000000feffffffaa5202bdf6d8ccd2ee0f0202afbbb7461d9264a25e5bfd3c5a52ee1239e0ba6c0000000000feffffff956149bdc66faa968eb2be2d2faa29718acbfe3941215893a2a3446d32acd050000000000000000000e664b9773b88c09c32cb70a2a3e4da0ced63b7ba3b22f848531bbb1d5d5f4c94010000000000000000e9aa6b8e6c9de67619e6a3924ae25696bb7b694bb677a632a74ef7eadfd4eabf0000000000ffffffffa778eb6a263dc090464cd125c466b5a99667720b1c110468831d058aa1b82af10100000000ffffffff0200ca9a3b000000001976a91406afd46bcdfd22ef94ac122aa11f241244a37ecc88ac807840cb0000000020ac9a87f5594be208f8532db38cff670c450ed2fea8fcdefcc9a663f78bab962b0065cd1d";
+
+    /// Outputs spent by the inputs of `TAPROOT_TX_HEX`
+    fn taproot_spent_outputs() -> Vec<TxOut> {
+        [
+            (
+                420_000_000,
+                "512053a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343",
+            ),
+            (
+                462_000_000,
+                "5120147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3",
+            ),
+            (
+                294_000_000,
+                "76a914751e76e8199196d454941c45d1b3a323f1433bd688ac",
+            ),
+            (
+                504_000_000,
+                "5120e4d810fd50586274face62b8a807eb9719cef49c04177cc6b76a9a4251d5450e",
+            ),
+            (
+                630_000_000,
+                "512091b64d5324723a985170e4dc5a0f84c041804f2cd12660fa5dec09fc21783605",
+            ),
+            (378_000_000, "00147dd65592d0ab2fe0d0257d571abf032cd9db93dc"),
+            (
+                672_000_000,
+                "512075169f4001aa68f15bbed28b218df1d0a62cbbcf1188c6665110c293c907b831",
+            ),
+            (
+                546_000_000,
+                "5120712447206d7a5238acc7ff53fbe94a3b64539ad291c7cdbc490b7577e4b17df5",
+            ),
+            (
+                588_000_000,
+                "512077e30a5522dd9f894c3f8b8bd4c4b2cf82ca7da8a3ea6a239655c39c050ab220",
+            ),
+        ]
+        .into_iter()
+        .map(|(value, script_pubkey)| TxOut::new(value, script_pubkey.to_bytes_be()))
+        .collect()
+    }
+
+    #[test]
+    fn test_taproot_sighash() {
+        let tx = Tx::deserialize(&TAPROOT_TX_HEX.to_bytes_be()).unwrap();
+        let spent_outputs = taproot_spent_outputs();
+        let cache = SighashCache::new(&tx).with_spent_outputs(&spent_outputs);
+
+        let expected = [
+            (
+                0,
+                SIGHASH_SINGLE,
+                "2514a6272f85cfa0f45eb907fcb0d121b808ed37c6ea160a5a9046ed5526d555",
+            ),
+            (
+                1,
+                SIGHASH_SINGLE | SIGHASH_ANYONECANPAY,
+                "325a644af47e8a5a2591cda0ab0723978537318f10e6a63d4eed783b96a71a4d",
+            ),
+            (
+                3,
+                SIGHASH_ALL,
+                "bf013ea93474aa67815b1b6cc441d23b64fa310911d991e713cd34c7f5d46669",
+            ),
+            (
+                4,
+                SIGHASH_DEFAULT,
+                "4f900a0bae3f1446fd48490c2958b5a023228f01661cda3496a11da502a7f7ef",
+            ),
+            (
+                6,
+                SIGHASH_NONE,
+                "15f25c298eb5cdc7eb1d638dd2d45c97c4c59dcaec6679cfc16ad84f30876b85",
+            ),
+            (
+                7,
+                SIGHASH_NONE | SIGHASH_ANYONECANPAY,
+                "cd292de50313804dabe4685e83f923d2969577191a3e1d2882220dca88cbeb10",
+            ),
+            (
+                8,
+                SIGHASH_ALL | SIGHASH_ANYONECANPAY,
+                "cccb739eca6c13a8a89e6e5cd317ffe55669bbda23f2fd37b0f18755e008edd2",
+            ),
+        ];
+        for (input_index, hash_type, sighash) in expected {
+            let computed = cache.taproot_sighash(input_index, hash_type, None, None);
+            assert_eq!(computed.unwrap().0.to_hex(), sighash, "input {input_index}");
+        }
+    }
+
+    #[test]
+    fn test_taproot_script_path_sighash() {
+        // Leaf of the script path vectors of BIP 341
+        let script =
+            "20b617298552a72ade070667e86ca63b8f5789a9fe8731ef91202a91c9f3459007ac".to_bytes_be();
+        let script_path = ScriptPath::from_script(&script);
+        assert_eq!(
+            script_path.leaf_hash.to_hex(),
+            "c525714a7f49c28aedbbba78c005931a81c234b2f6c99a73e4d06082adc8bf2b"
+        );
+        assert_eq!(
+            tap_leaf_hash(TAPSCRIPT_LEAF_VERSION, &script),
+            script_path.leaf_hash
+        );
+
+        let tx = Tx::deserialize(&TAPROOT_TX_HEX.to_bytes_be()).unwrap();
+        let spent_outputs = taproot_spent_outputs();
+        let cache = SighashCache::new(&tx).with_spent_outputs(&spent_outputs);
+        let sighash = |annex, script_path| {
+            cache
+                .taproot_sighash(3, SIGHASH_DEFAULT, annex, script_path)
+                .unwrap()
+        };
+
+        // The script path, its position of OP_CODESEPARATOR and the annex are
+        // committed to
+        let key_path = sighash(None, None);
+        let with_script = sighash(None, Some(&script_path));
+        let codeseparator = ScriptPath::new(script_path.leaf_hash, 0);
+        let other_leaf = ScriptPath::from_script(&[0x51]);
+        let annex = [0x50, 0x01];
+        let hashes = [
+            key_path,
+            with_script,
+            sighash(None, Some(&codeseparator)),
+            sighash(None, Some(&other_leaf)),
+            sighash(Some(&annex), None),
+            sighash(Some(&annex), Some(&script_path)),
+            sighash(Some(&[0x50]), None),
+        ];
+        for (index, hash) in hashes.iter().enumerate() {
+            assert!(!hashes[index + 1..].contains(hash));
+        }
+    }
+
+    #[test]
+    fn test_taproot_sighash_errors() {
+        let tx = Tx::deserialize(&TAPROOT_TX_HEX.to_bytes_be()).unwrap();
+        let spent_outputs = taproot_spent_outputs();
+        let cache = SighashCache::new(&tx).with_spent_outputs(&spent_outputs);
+
+        // Invalid hash types
+        for hash_type in [0x04, 0x80, 0x84, 0x101] {
+            assert!(cache.taproot_sighash(0, hash_type, None, None).is_err());
+        }
+
+        // SINGLE without an output with the index of the input
+        assert!(cache
+            .taproot_sighash(2, SIGHASH_SINGLE, None, None)
+            .is_err());
+        assert!(cache
+            .taproot_sighash(2, SIGHASH_SINGLE | SIGHASH_ANYONECANPAY, None, None)
+            .is_err());
+
+        assert!(cache.taproot_sighash(9, SIGHASH_ALL, None, None).is_err());
+
+        // The spent outputs are needed, one per input
+        let without_outputs = SighashCache::new(&tx);
+        assert!(without_outputs
+            .taproot_sighash(0, SIGHASH_ALL, None, None)
+            .is_err());
+        let missing_output = SighashCache::new(&tx).with_spent_outputs(&spent_outputs[1..]);
+        assert!(missing_output
+            .taproot_sighash(0, SIGHASH_ALL, None, None)
+            .is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use crate::encoding::encodable::{Decodable, Encodable};
+use color_eyre::eyre::Result;
+use std::io::{Read, Write};
+
+/// Represents a transaction output
+///
+/// - `value`: amount in satoshis
+/// - `script_pubkey`: the script locking it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOut {
+    pub value: u64,
+    pub script_pubkey: Vec<u8>,
+}
+
+impl TxOut {
+    pub fn new(value: u64, script_pubkey: Vec<u8>) -> Self {
+        Self {
+            value,
+            script_pubkey,
+        }
+    }
+}
+
+impl Encodable for TxOut {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        Ok(self.value.encode(writer)? + self.script_pubkey.encode(writer)?)
+    }
+}
+
+impl Decodable for TxOut {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        Ok(Self::new(
+            Decodable::decode(reader)?,
+            Decodable::decode(reader)?,
+        ))
+    }
+}
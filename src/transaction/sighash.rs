@@ -0,0 +1,531 @@
+use super::{output::TxOut, tx::Tx};
+use crate::{
+    elliptic_curve::secp256k1::Secp256k1Felt,
+    encoding::{
+        encodable::{Decodable, Encodable},
+        varint::write_varint,
+    },
+    helpers::{
+        bytes::ToHex,
+        hash::{hash256, tagged_hash},
+    },
+    script::{command::Command, opcode::Opcode},
+};
+use color_eyre::eyre::{eyre, Result};
+use sha2::{Digest, Sha256};
+use std::{cell::OnceCell, io::Write};
+
+/// Taproot only, signs like ALL without appending a hash type to the signature
+pub const SIGHASH_DEFAULT: u32 = 0x00;
+/// Signs all inputs and outputs
+pub const SIGHASH_ALL: u32 = 0x01;
+/// Signs all inputs and no outputs
+pub const SIGHASH_NONE: u32 = 0x02;
+/// Signs all inputs and the output with the same index as the input
+pub const SIGHASH_SINGLE: u32 = 0x03;
+/// Combined with the others, signs only the input being signed
+pub const SIGHASH_ANYONECANPAY: u32 = 0x80;
+
+/// Bits of the hash type selecting ALL, NONE or SINGLE
+const SIGHASH_OUTPUT_MASK: u32 = 0x1f;
+
+/// Leaf version of the tapscripts of BIP 342
+pub const TAPSCRIPT_LEAF_VERSION: u8 = 0xc0;
+
+/// The message digest a signature commits to, in the byte order it is hashed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sighash(pub [u8; 32]);
+
+impl Sighash {
+    /// The value legacy signing returns for invalid input indices and for SINGLE
+    /// without a matching output, 1 as a little endian 256 bit integer
+    pub fn one() -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 1;
+        Self(bytes)
+    }
+
+    /// Returns the digest as the message hash z `PrivateKey::sign` takes
+    pub fn z(&self) -> Secp256k1Felt {
+        Secp256k1Felt::from_bytes(&self.0)
+    }
+
+    /// Returns the digest as it is displayed, in reverse byte order
+    pub fn to_display(&self) -> String {
+        let mut bytes = self.0;
+        bytes.reverse();
+        bytes.to_hex()
+    }
+}
+
+impl Tx {
+    /// Computes the legacy signature hash of the input
+    ///
+    /// A copy of the transaction is serialized where
+    /// - the script of the signed input is replaced by `script_code`, without its
+    ///   `OP_CODESEPARATOR`s, and the scripts of the other inputs are emptied
+    /// - NONE drops the outputs, SINGLE keeps the outputs up to the one with the same
+    ///   index, with the previous ones blanked. Both zero the sequence of the other
+    ///   inputs
+    /// - ANYONECANPAY keeps only the signed input
+    ///
+    /// and the hash type is appended as 4 bytes before hashing with hash256.
+    ///
+    /// Like Bitcoin Core, returns `Sighash::one()` if the input index is out of range
+    /// or SINGLE has no output for the input, the `SIGHASH_SINGLE` bug. The witness
+    /// is never committed to
+    ///
+    /// # Panics
+    ///
+    /// Never in practice, writing to a vector does not fail
+    pub fn legacy_sighash(
+        &self,
+        input_index: usize,
+        script_code: &[u8],
+        hash_type: u32,
+    ) -> Sighash {
+        let hash_single = hash_type & SIGHASH_OUTPUT_MASK == SIGHASH_SINGLE;
+        if input_index >= self.inputs.len() || (hash_single && input_index >= self.outputs.len()) {
+            return Sighash::one();
+        }
+
+        let mut bytes = Vec::new();
+        self.encode_legacy_sighash(&mut bytes, input_index, script_code, hash_type)
+            .expect("Writing to a vector never fails");
+        Sighash(to_hash(&bytes))
+    }
+
+    fn encode_legacy_sighash<W: Write>(
+        &self,
+        writer: &mut W,
+        input_index: usize,
+        script_code: &[u8],
+        hash_type: u32,
+    ) -> Result<usize> {
+        let anyone_can_pay = hash_type & SIGHASH_ANYONECANPAY != 0;
+        let hash_none = hash_type & SIGHASH_OUTPUT_MASK == SIGHASH_NONE;
+        let hash_single = hash_type & SIGHASH_OUTPUT_MASK == SIGHASH_SINGLE;
+
+        let mut written = self.version.encode(writer)?;
+
+        let inputs = if anyone_can_pay {
+            input_index..input_index + 1
+        } else {
+            0..self.inputs.len()
+        };
+        written += write_varint(writer, inputs.len() as u64)?;
+        let script_code = remove_codeseparators(script_code);
+        for index in inputs {
+            let input = &self.inputs[index];
+            let signed = index == input_index;
+
+            written += input.previous_output.encode(writer)?;
+            written += if signed {
+                script_code.encode(writer)?
+            } else {
+                Vec::<u8>::new().encode(writer)?
+            };
+            let sequence = if !signed && (hash_none || hash_single) {
+                0
+            } else {
+                input.sequence
+            };
+            written += sequence.encode(writer)?;
+        }
+
+        let output_count = if hash_none {
+            0
+        } else if hash_single {
+            input_index + 1
+        } else {
+            self.outputs.len()
+        };
+        written += write_varint(writer, output_count as u64)?;
+        for (index, output) in self.outputs.iter().take(output_count).enumerate() {
+            written += if hash_single && index != input_index {
+                // Blank output, value of -1 and an empty script
+                TxOut::new(u64::MAX, Vec::new()).encode(writer)?
+            } else {
+                output.encode(writer)?
+            };
+        }
+
+        written += self.locktime.encode(writer)?;
+        written += hash_type.encode(writer)?;
+        Ok(written)
+    }
+}
+
+/// Computes the signature hashes of the inputs of a transaction
+///
+/// The hashes of the outpoints, sequences and outputs are the same for every input,
+/// so they are computed once, on first use, and reused across inputs
+#[derive(Debug)]
+pub struct SighashCache<'a> {
+    tx: &'a Tx,
+    prevouts: OnceCell<[u8; 32]>,
+    sequences: OnceCell<[u8; 32]>,
+    outputs: OnceCell<[u8; 32]>,
+    spent_outputs: Option<&'a [TxOut]>,
+    amounts: OnceCell<[u8; 32]>,
+    script_pubkeys: OnceCell<[u8; 32]>,
+}
+
+/// The tapscript leaf being executed in a taproot script path spend
+///
+/// - `leaf_hash`: the tagged hash of the leaf version and script
+/// - `codeseparator_position`: the opcode position of the last executed
+///   `OP_CODESEPARATOR`, or `u32::MAX` if there is none
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptPath {
+    pub leaf_hash: [u8; 32],
+    pub codeseparator_position: u32,
+}
+
+impl ScriptPath {
+    pub fn new(leaf_hash: [u8; 32], codeseparator_position: u32) -> Self {
+        Self {
+            leaf_hash,
+            codeseparator_position,
+        }
+    }
+
+    /// Spends the tapscript `script`, without any `OP_CODESEPARATOR` executed
+    pub fn from_script(script: &[u8]) -> Self {
+        Self::new(tap_leaf_hash(TAPSCRIPT_LEAF_VERSION, script), u32::MAX)
+    }
+}
+
+/// Computes the hash of a taproot leaf, the tagged hash of its version and script
+///
+/// # Panics
+///
+/// Never in practice, writing to a vector does not fail
+pub fn tap_leaf_hash(leaf_version: u8, script: &[u8]) -> [u8; 32] {
+    let mut bytes = vec![leaf_version];
+    script
+        .to_vec()
+        .encode(&mut bytes)
+        .expect("Writing to a vector never fails");
+    tagged_hash("TapLeaf", &bytes)
+}
+
+impl<'a> SighashCache<'a> {
+    pub fn new(tx: &'a Tx) -> Self {
+        Self {
+            tx,
+            prevouts: OnceCell::new(),
+            sequences: OnceCell::new(),
+            outputs: OnceCell::new(),
+            spent_outputs: None,
+            amounts: OnceCell::new(),
+            script_pubkeys: OnceCell::new(),
+        }
+    }
+
+    /// Sets the outputs spent by the inputs, in the same order, which taproot
+    /// signature hashes commit to
+    pub fn with_spent_outputs(mut self, spent_outputs: &'a [TxOut]) -> Self {
+        self.spent_outputs = Some(spent_outputs);
+        self.amounts = OnceCell::new();
+        self.script_pubkeys = OnceCell::new();
+        self
+    }
+
+    pub fn tx(&self) -> &'a Tx {
+        self.tx
+    }
+
+    /// Computes the signature hash of a segwit v0 input (BIP 143)
+    ///
+    /// Hashes with hash256 the version, the hashes of all outpoints, sequences and
+    /// outputs, the outpoint, `script_code`, spent `amount` and sequence of the input,
+    /// the locktime and the hash type. The hashes are replaced by zeros when the hash
+    /// type doesn't commit to them, and SINGLE hashes only the output with the same
+    /// index as the input, if there is one.
+    ///
+    /// `script_code` is the script being executed, without its length prefix. For
+    /// P2WPKH it is the P2PKH script of the key hash
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input index is out of range
+    ///
+    /// # Panics
+    ///
+    /// Never in practice, writing to a vector does not fail
+    pub fn segwit_v0_sighash(
+        &self,
+        input_index: usize,
+        script_code: &[u8],
+        amount: u64,
+        hash_type: u32,
+    ) -> Result<Sighash> {
+        let input = self.tx.inputs.get(input_index).ok_or_else(|| {
+            eyre!(
+                "Input index {input_index} out of range for {} inputs",
+                self.tx.inputs.len()
+            )
+        })?;
+
+        let anyone_can_pay = hash_type & SIGHASH_ANYONECANPAY != 0;
+        let hash_none = hash_type & SIGHASH_OUTPUT_MASK == SIGHASH_NONE;
+        let hash_single = hash_type & SIGHASH_OUTPUT_MASK == SIGHASH_SINGLE;
+
+        let hash_prevouts = if anyone_can_pay {
+            [0; 32]
+        } else {
+            sha256(self.sha_prevouts())
+        };
+        let hash_sequence = if anyone_can_pay || hash_none || hash_single {
+            [0; 32]
+        } else {
+            sha256(self.sha_sequences())
+        };
+        let hash_outputs = if !hash_none && !hash_single {
+            sha256(self.sha_outputs())
+        } else if hash_single && input_index < self.tx.outputs.len() {
+            to_hash(&self.tx.outputs[input_index].serialize())
+        } else {
+            [0; 32]
+        };
+
+        let mut bytes = Vec::new();
+        let mut write = || -> Result<usize> {
+            Ok(self.tx.version.encode(&mut bytes)?
+                + hash_prevouts.encode(&mut bytes)?
+                + hash_sequence.encode(&mut bytes)?
+                + input.previous_output.encode(&mut bytes)?
+                + script_code.to_vec().encode(&mut bytes)?
+                + amount.encode(&mut bytes)?
+                + input.sequence.encode(&mut bytes)?
+                + hash_outputs.encode(&mut bytes)?
+                + self.tx.locktime.encode(&mut bytes)?
+                + hash_type.encode(&mut bytes)?)
+        };
+        write().expect("Writing to a vector never fails");
+
+        Ok(Sighash(to_hash(&bytes)))
+    }
+
+    /// Computes the signature hash of a taproot input (BIP 341)
+    ///
+    /// Hashes with the `TapSighash` tagged hash the hash type, version, locktime, the
+    /// hashes of all outpoints, spent amounts, spent scripts, sequences and outputs,
+    /// the spend type, the input and the hash of the `annex`, if present. The input
+    /// is committed to by its index, or by its outpoint, spent output and sequence
+    /// with ANYONECANPAY, which also drops the hashes of all inputs. NONE drops the
+    /// hash of the outputs and SINGLE replaces it by the hash of the output with the
+    /// same index as the input.
+    ///
+    /// Key path spends pass no `script_path`. Script path spends also commit to the
+    /// leaf hash, key version and `OP_CODESEPARATOR` position (BIP 342).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if
+    /// - the spent outputs are not set or don't match the inputs
+    /// - the input index is out of range
+    /// - the hash type is not DEFAULT, ALL, NONE or SINGLE, optionally with
+    ///   ANYONECANPAY
+    /// - the hash type is SINGLE and there is no output with the input index
+    pub fn taproot_sighash(
+        &self,
+        input_index: usize,
+        hash_type: u32,
+        annex: Option<&[u8]>,
+        script_path: Option<&ScriptPath>,
+    ) -> Result<Sighash> {
+        let spent_outputs = self.spent_outputs()?;
+        let input = self.tx.inputs.get(input_index).ok_or_else(|| {
+            eyre!(
+                "Input index {input_index} out of range for {} inputs",
+                self.tx.inputs.len()
+            )
+        })?;
+        if !matches!(hash_type, 0x00..=0x03 | 0x81..=0x83) {
+            return Err(eyre!("Invalid taproot hash type {hash_type:#04x}"));
+        }
+
+        let anyone_can_pay = hash_type & SIGHASH_ANYONECANPAY != 0;
+        let hash_none = hash_type & 0x03 == SIGHASH_NONE;
+        let hash_single = hash_type & 0x03 == SIGHASH_SINGLE;
+
+        let single_output = if hash_single {
+            let output = self
+                .tx
+                .outputs
+                .get(input_index)
+                .ok_or_else(|| eyre!("No output for SIGHASH_SINGLE of input {input_index}"))?;
+            Some(output)
+        } else {
+            None
+        };
+
+        let mut bytes = Vec::new();
+        let mut write = || -> Result<usize> {
+            // Epoch of the signature hash
+            let mut written = 0u8.encode(&mut bytes)?;
+
+            written += u8::try_from(hash_type)?.encode(&mut bytes)?;
+            written += self.tx.version.encode(&mut bytes)?;
+            written += self.tx.locktime.encode(&mut bytes)?;
+            if !anyone_can_pay {
+                written += self.sha_prevouts().encode(&mut bytes)?;
+                written += self.sha_amounts(spent_outputs).encode(&mut bytes)?;
+                written += self.sha_script_pubkeys(spent_outputs).encode(&mut bytes)?;
+                written += self.sha_sequences().encode(&mut bytes)?;
+            }
+            if !hash_none && !hash_single {
+                written += self.sha_outputs().encode(&mut bytes)?;
+            }
+
+            let spend_type = u8::from(script_path.is_some()) * 2 + u8::from(annex.is_some());
+            written += spend_type.encode(&mut bytes)?;
+            if anyone_can_pay {
+                written += input.previous_output.encode(&mut bytes)?;
+                written += spent_outputs[input_index].encode(&mut bytes)?;
+                written += input.sequence.encode(&mut bytes)?;
+            } else {
+                written += u32::try_from(input_index)?.encode(&mut bytes)?;
+            }
+            if let Some(annex) = annex {
+                written += sha256(&annex.to_vec().serialize()).encode(&mut bytes)?;
+            }
+
+            if let Some(output) = single_output {
+                written += sha256(&output.serialize()).encode(&mut bytes)?;
+            }
+
+            if let Some(script_path) = script_path {
+                written += script_path.leaf_hash.encode(&mut bytes)?;
+                // Key version
+                written += 0u8.encode(&mut bytes)?;
+                written += script_path.codeseparator_position.encode(&mut bytes)?;
+            }
+            Ok(written)
+        };
+        write()?;
+
+        Ok(Sighash(tagged_hash("TapSighash", &bytes)))
+    }
+
+    fn spent_outputs(&self) -> Result<&'a [TxOut]> {
+        let spent_outputs = self
+            .spent_outputs
+            .ok_or_else(|| eyre!("Taproot signature hashes need the spent outputs"))?;
+        if spent_outputs.len() != self.tx.inputs.len() {
+            return Err(eyre!(
+                "{} spent outputs for {} inputs",
+                spent_outputs.len(),
+                self.tx.inputs.len()
+            ));
+        }
+        Ok(spent_outputs)
+    }
+
+    /// SHA256 of the serialized outpoints of all inputs
+    fn sha_prevouts(&self) -> &[u8; 32] {
+        self.prevouts.get_or_init(|| {
+            sha256_of(
+                self.tx
+                    .inputs
+                    .iter()
+                    .map(|input| input.previous_output.serialize()),
+            )
+        })
+    }
+
+    /// SHA256 of the sequences of all inputs
+    fn sha_sequences(&self) -> &[u8; 32] {
+        self.sequences.get_or_init(|| {
+            sha256_of(
+                self.tx
+                    .inputs
+                    .iter()
+                    .map(|input| input.sequence.serialize()),
+            )
+        })
+    }
+
+    /// SHA256 of the serialized outputs
+    fn sha_outputs(&self) -> &[u8; 32] {
+        self.outputs
+            .get_or_init(|| sha256_of(self.tx.outputs.iter().map(Encodable::serialize)))
+    }
+
+    /// SHA256 of the amounts of the spent outputs
+    fn sha_amounts(&self, spent_outputs: &[TxOut]) -> &[u8; 32] {
+        self.amounts
+            .get_or_init(|| sha256_of(spent_outputs.iter().map(|output| output.value.serialize())))
+    }
+
+    /// SHA256 of the scripts of the spent outputs, with their length prefixes
+    fn sha_script_pubkeys(&self, spent_outputs: &[TxOut]) -> &[u8; 32] {
+        self.script_pubkeys.get_or_init(|| {
+            sha256_of(
+                spent_outputs
+                    .iter()
+                    .map(|output| output.script_pubkey.serialize()),
+            )
+        })
+    }
+}
+
+impl Tx {
+    /// Computes the BIP 143 signature hash of a segwit v0 input
+    ///
+    /// Use a `SighashCache` to sign several inputs of the transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input index is out of range
+    pub fn segwit_v0_sighash(
+        &self,
+        input_index: usize,
+        script_code: &[u8],
+        amount: u64,
+        hash_type: u32,
+    ) -> Result<Sighash> {
+        SighashCache::new(self).segwit_v0_sighash(input_index, script_code, amount, hash_type)
+    }
+}
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// SHA256 of the concatenation of the items
+fn sha256_of(items: impl Iterator<Item = Vec<u8>>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for item in items {
+        hasher.update(item);
+    }
+    hasher.finalize().into()
+}
+
+fn to_hash(bytes: &[u8]) -> [u8; 32] {
+    hash256(bytes).try_into().expect("hash256 returns 32 bytes")
+}
+
+/// Removes the `OP_CODESEPARATOR`s of the script, skipping over pushed data
+///
+/// If a push runs past the end of the script, the rest is kept as it is
+fn remove_codeseparators(script: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(script.len());
+    let mut reader = script;
+
+    while !reader.is_empty() {
+        let start = script.len() - reader.len();
+        match Command::decode(&mut reader) {
+            Ok(Command::Op(Opcode::CodeSeparator)) => {}
+            Ok(_) => result.extend_from_slice(&script[start..script.len() - reader.len()]),
+            Err(_) => {
+                result.extend_from_slice(&script[start..]);
+                break;
+            }
+        }
+    }
+
+    result
+}
@@ -0,0 +1,175 @@
+use super::{input::TxIn, output::TxOut};
+use crate::{
+    encoding::{
+        encodable::{decode_items, Decodable, Encodable},
+        varint::read_length,
+    },
+    helpers::{bytes::ToHex, hash::hash256},
+};
+use color_eyre::eyre::{eyre, Result};
+use std::io::{Read, Write};
+
+/// Scale of the weight of non-witness data relative to witness data (BIP 141)
+pub const WITNESS_SCALE_FACTOR: usize = 4;
+
+/// Flag following the 0x00 marker of segwit serialization (BIP 144)
+const SEGWIT_FLAG: u8 = 0x01;
+
+/// Represents a Bitcoin transaction
+///
+/// Serialized as version, inputs, outputs and locktime. If any input has a witness,
+/// the segwit serialization of BIP 144 is used: the marker 0x00 and the flag 0x01
+/// follow the version and the witness stacks of the inputs come before the locktime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tx {
+    pub version: i32,
+    pub inputs: Vec<TxIn>,
+    pub outputs: Vec<TxOut>,
+    pub locktime: u32,
+}
+
+impl Tx {
+    pub fn new(version: i32, inputs: Vec<TxIn>, outputs: Vec<TxOut>, locktime: u32) -> Self {
+        Self {
+            version,
+            inputs,
+            outputs,
+            locktime,
+        }
+    }
+
+    /// Checks if any input has witness data
+    pub fn has_witness(&self) -> bool {
+        self.inputs.iter().any(|input| !input.witness.is_empty())
+    }
+
+    /// Returns the legacy serialization, without the witness data
+    ///
+    /// # Panics
+    ///
+    /// Never in practice, writing to a vector does not fail
+    pub fn serialize_without_witness(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.encode_with(&mut bytes, false)
+            .expect("Writing to a vector never fails");
+        bytes
+    }
+
+    /// Returns the hash of the transaction in the byte order outpoints refer to it
+    ///
+    /// The witness is not committed to, so the txid can't be changed by the witness
+    pub fn txid(&self) -> [u8; 32] {
+        to_hash(&self.serialize_without_witness())
+    }
+
+    /// Returns the transaction id as it is displayed, the txid in reverse byte order
+    pub fn id(&self) -> String {
+        display(self.txid())
+    }
+
+    /// Returns the hash of the transaction with its witness data, the same as the
+    /// txid for transactions without witness
+    pub fn wtxid(&self) -> [u8; 32] {
+        to_hash(&self.serialize())
+    }
+
+    /// Returns the wtxid as it is displayed, in reverse byte order
+    pub fn witness_id(&self) -> String {
+        display(self.wtxid())
+    }
+
+    /// Size of the serialization without witness data, in bytes
+    pub fn base_size(&self) -> usize {
+        self.serialize_without_witness().len()
+    }
+
+    /// Size of the full serialization, in bytes
+    pub fn total_size(&self) -> usize {
+        self.serialize().len()
+    }
+
+    /// Weight of the transaction, base size * 3 + total size (BIP 141)
+    pub fn weight(&self) -> usize {
+        self.base_size() * (WITNESS_SCALE_FACTOR - 1) + self.total_size()
+    }
+
+    /// Virtual size of the transaction, weight / 4 rounded up, used for fee rates
+    pub fn vsize(&self) -> usize {
+        self.weight().div_ceil(WITNESS_SCALE_FACTOR)
+    }
+
+    /// Checks if the transaction is a coinbase, with a single input spending nothing
+    pub fn is_coinbase(&self) -> bool {
+        self.inputs.len() == 1 && self.inputs[0].previous_output.is_null()
+    }
+
+    fn encode_with<W: Write>(&self, writer: &mut W, include_witness: bool) -> Result<usize> {
+        let segwit = include_witness && self.has_witness();
+
+        let mut written = self.version.encode(writer)?;
+        if segwit {
+            written += [0u8, SEGWIT_FLAG].encode(writer)?;
+        }
+        written += self.inputs.encode(writer)?;
+        written += self.outputs.encode(writer)?;
+        if segwit {
+            for input in &self.inputs {
+                written += input.witness.encode(writer)?;
+            }
+        }
+        written += self.locktime.encode(writer)?;
+
+        Ok(written)
+    }
+}
+
+impl Encodable for Tx {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        self.encode_with(writer, true)
+    }
+}
+
+impl Decodable for Tx {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self> {
+        let version = i32::decode(reader)?;
+
+        // An empty input list is the marker of segwit serialization
+        let mut input_count = read_length(reader)?;
+        let segwit = input_count == 0;
+        if segwit {
+            let flag = u8::decode(reader)?;
+            if flag != SEGWIT_FLAG {
+                return Err(eyre!("Unknown segwit flag {flag:#04x}"));
+            }
+            input_count = read_length(reader)?;
+        }
+
+        let mut inputs: Vec<TxIn> = decode_items(reader, input_count)?;
+        let outputs = Vec::<TxOut>::decode(reader)?;
+
+        if segwit {
+            for input in &mut inputs {
+                input.witness = Decodable::decode(reader)?;
+            }
+
+            // Would serialize without the marker, so it wouldn't round trip
+            if inputs.iter().all(|input| input.witness.is_empty()) {
+                return Err(eyre!("Segwit serialization without witness data"));
+            }
+        }
+
+        let locktime = u32::decode(reader)?;
+        Ok(Self::new(version, inputs, outputs, locktime))
+    }
+}
+
+/// Double SHA256 of the serialization
+fn to_hash(bytes: &[u8]) -> [u8; 32] {
+    hash256(bytes).try_into().expect("hash256 returns 32 bytes")
+}
+
+/// Reverses the hash for display
+fn display(mut hash: [u8; 32]) -> String {
+    hash.reverse();
+    hash.to_hex()
+}